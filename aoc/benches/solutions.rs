@@ -0,0 +1,31 @@
+use std::{fs, hint::black_box, path::Path};
+
+use aoc_common::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Benchmarks parsing and both parts separately against the day's real input.
+fn bench_solution<S: Solution>(c: &mut Criterion, day: u8, solution: S) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day_{day}")).join("src").join("input.txt");
+    let input = fs::read_to_string(path).expect("Failed reading file");
+    let parsed = solution.parse(&input).expect("Failed parsing input");
+
+    let mut group = c.benchmark_group(format!("day_{day}"));
+    group.bench_function("parse", |b| b.iter(|| solution.parse(black_box(&input))));
+    group.bench_function("part_one", |b| b.iter(|| solution.part_one(black_box(&parsed))));
+    group.bench_function("part_two", |b| b.iter(|| solution.part_two(black_box(&parsed))));
+    group.finish();
+}
+
+fn solutions(c: &mut Criterion) {
+    bench_solution(c, 1, day_1::CalorieCounting);
+    bench_solution(c, 2, day_2::RockPaperScissors::default());
+    bench_solution(c, 3, day_3::RucksackReorganization);
+    bench_solution(c, 4, day_4::CampCleanup);
+    bench_solution(c, 5, day_5::SupplyStacks);
+    bench_solution(c, 6, day_6::TuningTrouble);
+    bench_solution(c, 7, day_7::NoSpaceLeftOnDevice);
+    bench_solution(c, 8, day_8::TreetopTreeHouse);
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
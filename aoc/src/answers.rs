@@ -0,0 +1,74 @@
+use std::{error::Error, fs, path::Path};
+
+use toml::{Table, Value};
+
+const PART_KEYS: [&str; 2] = ["part_one", "part_two"];
+
+/// Known-good answers for a day, as stored in the `answers.toml` next to its input.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+        let table: Table = contents.parse()?;
+        let mut answers = Answers::default();
+        for (i, key) in PART_KEYS.iter().enumerate() {
+            answers.parts[i] = match table.get(*key) {
+                None => None,
+                Some(Value::String(answer)) => Some(answer.clone()),
+                Some(Value::Integer(answer)) => Some(answer.to_string()),
+                Some(value) => return Err(format!("Unsupported answer for {key}: {value}").into()),
+            };
+        }
+        Ok(answers)
+    }
+
+    /// A missing file means nothing has been recorded yet.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        Self::parse(&fs::read_to_string(path)?).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut table = Table::new();
+        for (key, answer) in PART_KEYS.iter().zip(&self.parts) {
+            if let Some(answer) = answer {
+                table.insert(String::from(*key), Value::String(answer.clone()));
+            }
+        }
+        fs::write(path, toml::to_string(&table)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        self.parts[part as usize - 1].as_deref()
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        self.parts[part as usize - 1] = Some(answer);
+    }
+}
+
+#[test]
+fn parse_answers_test() {
+    let answers = Answers::parse("part_one = 65912\npart_two = \"FGLQJCMBD\"\n").unwrap();
+    assert_eq!(answers.get(1), Some("65912"));
+    assert_eq!(answers.get(2), Some("FGLQJCMBD"));
+    assert_eq!(Answers::parse("part_two = 1").unwrap().get(1), None);
+    assert!(Answers::parse("part_one = [1]").is_err());
+}
+
+#[test]
+fn save_and_load_answers_test() {
+    let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+    let mut answers = Answers::default();
+    answers.set(1, String::from("TLNGFGMFN"));
+    answers.set(2, String::from("a \"quoted\" answer"));
+    answers.save(&path).unwrap();
+    assert_eq!(Answers::load(&path).unwrap(), answers);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,266 @@
+use std::{error::Error, path::{Path, PathBuf}, process, time::{Duration, Instant}};
+
+use aoc_common::{InputArgs, InputSource, Solution};
+use answers::Answers;
+use clap::{Parser, Subcommand};
+use report::{AnswerRecord, Format};
+
+mod answers;
+mod report;
+
+const DAYS: u8 = 8;
+const PART_NAMES: [&str; 2] = ["one", "two"];
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, optionally limited to one part
+    Run {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=DAYS as i64))]
+        day: u8,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Report how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+        /// Output format; json and csv always include timings
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Run both parts of every day against their default inputs
+    All {
+        /// Report how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+        /// Output format; json and csv always include timings
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Compare every part against the answers recorded in each day's answers.toml
+    Verify {
+        /// Only verify this day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=DAYS as i64))]
+        day: Option<u8>,
+    },
+    /// Record the current answers as known-good in each day's answers.toml
+    Record {
+        /// Only record this day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=DAYS as i64))]
+        day: Option<u8>,
+    },
+}
+
+fn day_directory(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day_{day}")).join("src")
+}
+
+fn default_input(day: u8) -> PathBuf {
+    day_directory(day).join("input.txt")
+}
+
+fn answers_path(day: u8) -> PathBuf {
+    day_directory(day).join("answers.toml")
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+struct PartRun {
+    part: u8,
+    /// The answer, or why the part failed.
+    answer: Result<String, String>,
+    time: Duration,
+}
+
+struct DayRun {
+    parse_time: Duration,
+    parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    /// Fails if any part did.
+    fn answers(self) -> Result<Vec<String>, Box<dyn Error>> {
+        match self.failures() {
+            Some(failures) => Err(failures.into()),
+            None => Ok(self.parts.into_iter().filter_map(|p| p.answer.ok()).collect()),
+        }
+    }
+
+    /// Every failed part with its error, or `None` if all of them succeeded.
+    fn failures(&self) -> Option<String> {
+        let failures = self.parts.iter()
+            .filter_map(|p| Some(format!("part {}: {}", PART_NAMES[p.part as usize - 1], p.answer.as_ref().err()?)))
+            .collect::<Vec<_>>();
+        (!failures.is_empty()).then(|| failures.join("; "))
+    }
+
+    fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+fn solve<S: Solution>(solution: &S, parts: &[u8], input: &str) -> Result<DayRun, Box<dyn Error>> {
+    let start = Instant::now();
+    let parsed = solution.parse(input)?;
+    let parse_time = start.elapsed();
+    let mut runs = vec![];
+    for &part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => solution.part_one(&parsed).map(|answer| answer.to_string()),
+            _ => solution.part_two(&parsed).map(|answer| answer.to_string()),
+        };
+        let answer = answer.map_err(|e| e.to_string());
+        runs.push(PartRun { part, answer, time: start.elapsed() });
+    }
+    Ok(DayRun { parse_time, parts: runs })
+}
+
+fn solve_day(day: u8, parts: &[u8], input: &InputSource) -> Result<DayRun, Box<dyn Error>> {
+    let input = &input.read().map_err(|e| format!("Failed reading input: {e}"))?;
+    match day {
+        1 => solve(&day_1::CalorieCounting, parts, input),
+        2 => solve(&day_2::RockPaperScissors::default(), parts, input),
+        3 => solve(&day_3::RucksackReorganization, parts, input),
+        4 => solve(&day_4::CampCleanup, parts, input),
+        5 => solve(&day_5::SupplyStacks, parts, input),
+        6 => solve(&day_6::TuningTrouble, parts, input),
+        7 => solve(&day_7::NoSpaceLeftOnDevice, parts, input),
+        8 => solve(&day_8::TreetopTreeHouse, parts, input),
+        _ => unreachable!("Day {day} is not implemented"),
+    }
+}
+
+/// Prints the text report right away; other formats are collected into `records` and printed at the end.
+///
+/// Parts that succeeded are reported even when another part fails; the failures are returned as the error.
+fn run_day(day: u8, part: Option<u8>, input: &InputSource, time: bool, format: Format, records: &mut Vec<AnswerRecord>) -> Result<Duration, Box<dyn Error>> {
+    let run = solve_day(day, &parts(part), input)?;
+    if format != Format::Text {
+        records.extend(run.parts.iter()
+            .filter_map(|p| Some(AnswerRecord::new(day, p.part, p.answer.clone().ok()?, run.parse_time, p.time))));
+    } else {
+        for p in &run.parts {
+            if let Ok(answer) = &p.answer {
+                println!("Part {}: {answer}", PART_NAMES[p.part as usize - 1]);
+            }
+        }
+        if time {
+            let part_times = run.parts.iter()
+                .map(|p| format!("part {} {:?}", PART_NAMES[p.part as usize - 1], p.time))
+                .collect::<Vec<_>>();
+            println!("Timing: parse {:?}, {}, total {:?}", run.parse_time, part_times.join(", "), run.total_time());
+        }
+    }
+    match run.failures() {
+        Some(failures) => Err(failures.into()),
+        None => Ok(run.total_time()),
+    }
+}
+
+/// Fails if any recorded answer for the day no longer matches.
+fn verify_day(day: u8) -> Result<(), Box<dyn Error>> {
+    let expected = Answers::load(&answers_path(day))?;
+    let answers = solve_day(day, &[1, 2], &InputSource::File(default_input(day)))?.answers()?;
+    let mut mismatches = 0;
+    for (part, actual) in [1, 2].into_iter().zip(answers) {
+        let label = format!("Day {day} part {}", PART_NAMES[part as usize - 1]);
+        match expected.get(part) {
+            None => println!("{label}: no recorded answer (got {actual})"),
+            Some(expected) if expected == actual => println!("{label}: ok"),
+            Some(expected) => {
+                mismatches += 1;
+                println!("{label}: FAILED");
+                println!("  - expected: {expected}");
+                println!("  + actual:   {actual}");
+            },
+        }
+    }
+    match mismatches {
+        0 => Ok(()),
+        _ => Err(format!("{mismatches} answer(s) changed").into()),
+    }
+}
+
+fn record_day(day: u8) -> Result<(), Box<dyn Error>> {
+    let path = answers_path(day);
+    let mut answers = Answers::load(&path)?;
+    let actual = solve_day(day, &[1, 2], &InputSource::File(default_input(day)))?.answers()?;
+    for (part, answer) in [1, 2].into_iter().zip(actual) {
+        answers.set(part, answer);
+    }
+    answers.save(&path)?;
+    println!("Recorded day {day} answers in {}", path.display());
+    Ok(())
+}
+
+fn selected_days(day: Option<u8>) -> Vec<u8> {
+    match day {
+        Some(day) => vec![day],
+        None => (1..=DAYS).collect(),
+    }
+}
+
+fn print_records(format: Format, records: &[AnswerRecord]) {
+    match format {
+        Format::Text => (),
+        Format::Json => println!("{}", report::to_json(records)),
+        Format::Csv => print!("{}", report::to_csv(records)),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let mut failed = false;
+    let mut report = |day: u8, result: Result<(), Box<dyn Error>>| {
+        if let Err(error) = result {
+            eprintln!("Error: day {day}: {error}");
+            failed = true;
+        }
+    };
+    let mut records = vec![];
+    match cli.command {
+        Command::Run { day, part, input, time, format } => {
+            report(day, run_day(day, part, &input.source(default_input(day)), time, format, &mut records).map(|_| ()));
+            print_records(format, &records);
+        },
+        Command::All { time, format } => {
+            let mut total = Duration::ZERO;
+            for day in 1..=DAYS {
+                if format == Format::Text {
+                    println!("Day {day}");
+                }
+                report(day, run_day(day, None, &InputSource::File(default_input(day)), time, format, &mut records).map(|elapsed| total += elapsed));
+            }
+            if time && format == Format::Text {
+                println!("Total time: {total:?}");
+            }
+            print_records(format, &records);
+        },
+        Command::Verify { day } => {
+            for day in selected_days(day) {
+                report(day, verify_day(day));
+            }
+        },
+        Command::Record { day } => {
+            for day in selected_days(day) {
+                report(day, record_day(day));
+            }
+        },
+    }
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Human-readable sentences
+    #[default]
+    Text,
+    /// A single JSON array with one object per solved part
+    Json,
+    /// A header line followed by one row per solved part
+    Csv,
+}
+
+/// One solved part, as emitted by the machine-readable formats.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnswerRecord {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Time spent parsing the day's input, shared by both of its parts.
+    pub parse_time_ns: u64,
+    pub time_ns: u64,
+}
+
+impl AnswerRecord {
+    pub fn new(day: u8, part: u8, answer: String, parse_time: Duration, time: Duration) -> Self {
+        Self { day, part, answer, parse_time_ns: parse_time.as_nanos() as u64, time_ns: time.as_nanos() as u64 }
+    }
+}
+
+pub fn to_json(records: &[AnswerRecord]) -> String {
+    serde_json::to_string_pretty(records).expect("Answer records are always serializable")
+}
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+pub fn to_csv(records: &[AnswerRecord]) -> String {
+    let mut csv = String::from("day,part,answer,parse_time_ns,time_ns\n");
+    for r in records {
+        csv += &format!("{},{},{},{},{}\n", r.day, r.part, csv_field(&r.answer), r.parse_time_ns, r.time_ns);
+    }
+    csv
+}
+
+#[test]
+fn to_csv_test() {
+    let records = [
+        AnswerRecord::new(1, 1, String::from("24000"), Duration::from_nanos(5), Duration::from_nanos(7)),
+        AnswerRecord::new(5, 2, String::from("M,\"D"), Duration::from_nanos(5), Duration::from_nanos(9)),
+    ];
+    assert_eq!(to_csv(&records), "day,part,answer,parse_time_ns,time_ns\n1,1,24000,5,7\n5,2,\"M,\"\"D\",5,9\n");
+}
+
+#[test]
+fn to_json_test() {
+    let records = [AnswerRecord::new(6, 2, String::from("19"), Duration::from_nanos(3), Duration::from_nanos(4))];
+    let json: serde_json::Value = serde_json::from_str(&to_json(&records)).unwrap();
+    assert_eq!(json, serde_json::json!([{ "day": 6, "part": 2, "answer": "19", "parse_time_ns": 3, "time_ns": 4 }]));
+}
@@ -0,0 +1,43 @@
+use std::{error::Error, fmt::{self, Display}};
+
+/// An error tied to the input line that caused it. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError<E> {
+    pub line: usize,
+    pub text: String,
+    pub kind: E,
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} (`{}`)", self.line, self.kind, self.text)
+    }
+}
+
+impl<E: Error + 'static> Error for LineError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+pub trait LineContext<T, E> {
+    fn at_line(self, line: usize, text: &str) -> Result<T, LineError<E>>;
+}
+
+impl<T, E> LineContext<T, E> for Result<T, E> {
+    fn at_line(self, line: usize, text: &str) -> Result<T, LineError<E>> {
+        self.map_err(|kind| LineError { line, text: text.to_owned(), kind })
+    }
+}
+
+/// Iterates over the lines of `input` together with their 1-based line numbers.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+#[test]
+fn line_error_display_test() {
+    let error: Result<(), _> = Err("unknown symbol");
+    let error = error.at_line(3, "M X").unwrap_err();
+    assert_eq!(error.to_string(), "line 3: unknown symbol (`M X`)");
+}
@@ -0,0 +1,89 @@
+use std::{env, fs::{self, File}, io::{self, BufRead, BufReader, Cursor, Read}, path::PathBuf};
+
+use clap::Args;
+
+/// Environment variable consulted when no input is given on the command line.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// `-` reads from stdin, anything else is treated as a file path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            },
+            InputSource::Inline(contents) => Ok(contents.clone()),
+        }
+    }
+
+    /// Buffered line-by-line access for days that can process their input without loading it whole.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Inline(contents) => Ok(Box::new(Cursor::new(contents.clone().into_bytes()))),
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// Puzzle input file, or `-` to read from stdin; falls back to $AOC_INPUT
+    #[arg(short, long)]
+    pub input: Option<String>,
+    /// Puzzle input passed directly on the command line
+    #[arg(long, conflicts_with = "input")]
+    pub inline: Option<String>,
+}
+
+impl InputArgs {
+    /// Picks `--inline`, then `--input`, then `$AOC_INPUT`, and finally `default`.
+    pub fn source(&self, default: impl Into<PathBuf>) -> InputSource {
+        if let Some(contents) = &self.inline {
+            InputSource::Inline(contents.clone())
+        } else if let Some(arg) = &self.input {
+            InputSource::from_arg(arg)
+        } else if let Ok(arg) = env::var(INPUT_ENV_VAR) {
+            InputSource::from_arg(&arg)
+        } else {
+            InputSource::File(default.into())
+        }
+    }
+}
+
+#[test]
+fn from_arg_test() {
+    assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+    assert_eq!(InputSource::from_arg("day_1/src/input.txt"), InputSource::File(PathBuf::from("day_1/src/input.txt")));
+}
+
+#[test]
+fn source_precedence_test() {
+    let args = InputArgs { input: Some(String::from("-")), inline: None };
+    assert_eq!(args.source("default.txt"), InputSource::Stdin);
+    let args = InputArgs { input: None, inline: Some(String::from("1\n2")) };
+    assert_eq!(args.source("default.txt"), InputSource::Inline(String::from("1\n2")));
+}
+
+#[test]
+fn inline_reader_test() {
+    let lines = InputSource::Inline(String::from("1\n2")).reader().unwrap().lines().collect::<io::Result<Vec<_>>>().unwrap();
+    assert_eq!(lines, vec!["1", "2"]);
+}
@@ -0,0 +1,18 @@
+use std::{error::Error, fmt::Display};
+
+pub mod error;
+pub mod input;
+
+pub use error::{numbered_lines, LineContext, LineError};
+pub use input::{InputArgs, InputSource};
+
+/// A single day's puzzle: the input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input;
+    type Answer: Display;
+    type Error: Error + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error>;
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, Self::Error>;
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, Self::Error>;
+}
@@ -0,0 +1,261 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::{self, BufRead}, num::{NonZeroUsize, ParseIntError}};
+
+use aoc_common::{LineContext, LineError, Solution};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CalorieError {
+    #[error("Invalid calories on {0}")]
+    InvalidCalories(#[from] LineError<ParseIntError>),
+    #[error("Calories of elf {elf} overflow on line {line}")]
+    Overflow { elf: usize, line: usize },
+    #[error("Calories of the top {0} elves overflow")]
+    TotalOverflow(usize),
+    #[error("Failed reading input: {0}")]
+    Io(#[from] io::Error),
+    #[error("Expected at least {required} elves, found {found}")]
+    NotEnoughElves { required: usize, found: usize },
+}
+
+/// The `n` largest items with their zero-based positions, largest first. Equal items keep input order.
+///
+/// Only `n` items are held at a time, so this works on inventories too large to sort.
+pub fn top_n<T: Ord>(items: impl IntoIterator<Item = T>, n: usize) -> Vec<(usize, T)> {
+    if n == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for (i, item) in items.into_iter().enumerate() {
+        heap.push(Reverse((item, Reverse(i))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|Reverse((item, Reverse(i)))| (i, item)).collect()
+}
+
+#[test]
+fn top_n_test() {
+    let calories = [6000, 4000, 11000, 24000, 10000];
+    assert_eq!(top_n(calories, 3), vec![(3, 24000), (2, 11000), (4, 10000)]);
+    assert_eq!(top_n(calories, 1), vec![(3, 24000)]);
+    assert_eq!(top_n(calories, 0), vec![]);
+    assert_eq!(top_n(calories, 10).len(), 5);
+    assert_eq!(top_n([5, 7, 5, 7], 3), vec![(1, 7), (3, 7), (0, 5)]);
+}
+
+/// A single elf's food: how many items it carries and their total calories.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Inventory {
+    items: usize,
+    calories: u64,
+}
+
+impl Inventory {
+    pub fn new(items: usize, calories: u64) -> Self {
+        Self { items, calories }
+    }
+
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    pub fn calories(&self) -> u64 {
+        self.calories
+    }
+}
+
+/// Reads `reader` line by line into one inventory per elf, so the input never has to be held in memory.
+///
+/// Any run of blank lines separates two elves, and `\r\n` line endings are accepted.
+pub fn parse_inventories<R: BufRead>(mut reader: R) -> Result<Vec<Inventory>, CalorieError> {
+    let mut inventories = vec![];
+    let mut current: Option<Inventory> = None;
+    let mut buffer = String::new();
+    let mut line = 0;
+    while reader.read_line(&mut buffer)? > 0 {
+        line += 1;
+        let item = buffer.trim_end_matches(['\n', '\r']);
+        if item.trim().is_empty() {
+            inventories.extend(current.take());
+        } else {
+            let calories = item.trim().parse::<u64>().at_line(line, item)?;
+            let elf = inventories.len() + 1;
+            let Inventory { items, calories: total } = current.unwrap_or(Inventory::new(0, 0));
+            let total = total.checked_add(calories).ok_or(CalorieError::Overflow { elf, line })?;
+            current = Some(Inventory::new(items + 1, total));
+        }
+        buffer.clear();
+    }
+    inventories.extend(current);
+    Ok(inventories)
+}
+
+/// Total calories of each elf, see [`parse_inventories`].
+pub fn parse_grouped_calories<R: BufRead>(reader: R) -> Result<Vec<u64>, CalorieError> {
+    Ok(parse_inventories(reader)?.iter().map(Inventory::calories).collect())
+}
+
+#[test]
+fn parse_grouped_calories_test() {
+    assert_eq!(parse_grouped_calories("1000\n2000\n\n3000\n".as_bytes()).unwrap(), vec![3000, 3000]);
+    assert_eq!(parse_grouped_calories("1000\r\n2000\r\n\r\n3000".as_bytes()).unwrap(), vec![3000, 3000]);
+    assert_eq!(parse_grouped_calories("\n1000\n\n\n\n3000\n\n".as_bytes()).unwrap(), vec![1000, 3000]);
+    assert_eq!(parse_grouped_calories("".as_bytes()).unwrap(), vec![]);
+}
+
+#[test]
+fn parse_grouped_calories_overflow_test() {
+    let input = format!("1\n\n{}\n1", u64::MAX);
+    let error = parse_grouped_calories(input.as_bytes()).unwrap_err();
+    assert_eq!(error.to_string(), "Calories of elf 2 overflow on line 4");
+}
+
+#[test]
+fn parse_inventories_test() {
+    let inventories = parse_inventories("1000\n2000\n\n3000\n".as_bytes()).unwrap();
+    assert_eq!(inventories, vec![Inventory::new(2, 3000), Inventory::new(1, 3000)]);
+}
+
+pub struct CalorieCounting;
+
+impl Solution for CalorieCounting {
+    type Input = Vec<u64>;
+    type Answer = u64;
+    type Error = CalorieError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        parse_grouped_calories(input.as_bytes())
+    }
+
+    fn part_one(&self, grouped_calories: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        top_n_sum(grouped_calories, 1)
+    }
+
+    fn part_two(&self, grouped_calories: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        top_n_sum(grouped_calories, 3)
+    }
+}
+
+/// Total calories carried by the `n` elves carrying the most.
+pub fn top_n_sum(grouped_calories: &[u64], n: usize) -> Result<u64, CalorieError> {
+    if grouped_calories.len() < n {
+        return Err(CalorieError::NotEnoughElves { required: n, found: grouped_calories.len() });
+    }
+    top_n(grouped_calories.iter().copied(), n).iter()
+        .try_fold(0u64, |acc, &(_, calories)| acc.checked_add(calories))
+        .ok_or(CalorieError::TotalOverflow(n))
+}
+
+#[test]
+fn parse_reports_invalid_line_test() {
+    let error = CalorieCounting.parse("1000\n2000\n\n3x00").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid calories on line 4: invalid digit found in string (`3x00`)");
+}
+
+/// Percentiles reported by [`calorie_stats`].
+pub const STATS_PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Elves whose total calories fall within `from..=to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBin {
+    pub from: u64,
+    pub to: u64,
+    pub elves: usize,
+}
+
+/// Descriptive statistics over every elf's calories and item counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieStats {
+    pub elves: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Nearest-rank percentiles, one per entry of [`STATS_PERCENTILES`].
+    pub percentiles: Vec<(u8, u64)>,
+    /// Number of items carried by each elf, in input order.
+    pub items: Vec<usize>,
+    pub histogram: Vec<HistogramBin>,
+}
+
+/// Nearest-rank percentile of already sorted values.
+pub fn percentile(sorted: &[u64], percent: u8) -> Option<u64> {
+    let rank = (sorted.len() * percent as usize).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Splits `min..=max` into `bins` equally wide bins and counts the elves in each.
+fn histogram(sorted: &[u64], bins: NonZeroUsize) -> Vec<HistogramBin> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = ((max - min) / bins.get() as u64).saturating_add(1);
+    let mut histogram = (0..bins.get() as u64)
+        .map(|i| {
+            let from = min.saturating_add(i.saturating_mul(width));
+            HistogramBin { from, to: from.saturating_add(width - 1), elves: 0 }
+        })
+        .collect::<Vec<_>>();
+    // A saturated width can leave `max` one bin past the end, so the last bin stretches to take it.
+    if let Some(last) = histogram.last_mut() {
+        last.to = last.to.max(max);
+    }
+    for &calories in sorted {
+        histogram[(((calories - min) / width) as usize).min(bins.get() - 1)].elves += 1;
+    }
+    histogram
+}
+
+pub fn calorie_stats(inventories: &[Inventory], bins: NonZeroUsize) -> Result<CalorieStats, CalorieError> {
+    if inventories.is_empty() {
+        return Err(CalorieError::NotEnoughElves { required: 1, found: 0 });
+    }
+    let mut sorted = inventories.iter().map(Inventory::calories).collect::<Vec<_>>();
+    sorted.sort_unstable();
+    let elves = sorted.len();
+    let middle = elves / 2;
+    let median = match elves % 2 {
+        0 => (sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.0,
+        _ => sorted[middle] as f64,
+    };
+    Ok(CalorieStats {
+        elves,
+        min: sorted[0],
+        max: sorted[elves - 1],
+        mean: sorted.iter().map(|&c| c as f64).sum::<f64>() / elves as f64,
+        median,
+        percentiles: STATS_PERCENTILES.iter().filter_map(|&p| Some((p, percentile(&sorted, p)?))).collect(),
+        items: inventories.iter().map(Inventory::items).collect(),
+        histogram: histogram(&sorted, bins),
+    })
+}
+
+#[test]
+fn calorie_stats_test() {
+    let inventories = parse_inventories("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000".as_bytes()).unwrap();
+    let bins = |n| NonZeroUsize::new(n).unwrap();
+    let stats = calorie_stats(&inventories, bins(4)).unwrap();
+    assert_eq!(stats.elves, 5);
+    assert_eq!((stats.min, stats.max), (4000, 24000));
+    assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+    assert_eq!(stats.percentiles, vec![(10, 4000), (25, 6000), (75, 11000), (90, 24000), (99, 24000)]);
+    assert_eq!(stats.items, vec![3, 1, 2, 3, 1]);
+    assert_eq!(stats.histogram.iter().map(|bin| bin.elves).collect::<Vec<_>>(), vec![2, 2, 0, 1]);
+    assert_eq!((stats.histogram[0].from, stats.histogram[0].to), (4000, 9000));
+    assert!(calorie_stats(&[], bins(4)).is_err());
+}
+
+#[test]
+fn calorie_stats_even_median_test() {
+    let stats = calorie_stats(&[Inventory::new(1, 10), Inventory::new(1, 20)], NonZeroUsize::MIN).unwrap();
+    assert_eq!(stats.median, 15.0);
+    assert_eq!(stats.histogram, vec![HistogramBin { from: 10, to: 20, elves: 2 }]);
+}
+
+#[test]
+fn calorie_stats_full_range_test() {
+    let inventories = [Inventory::new(1, 0), Inventory::new(1, u64::MAX)];
+    let stats = calorie_stats(&inventories, NonZeroUsize::MIN).unwrap();
+    assert_eq!(stats.histogram, vec![HistogramBin { from: 0, to: u64::MAX, elves: 2 }]);
+    let stats = calorie_stats(&inventories, NonZeroUsize::new(2).unwrap()).unwrap();
+    assert_eq!(stats.histogram.iter().map(|bin| bin.elves).collect::<Vec<_>>(), vec![1, 1]);
+}
@@ -1,13 +1,62 @@
-use std::fs;
+use std::{error::Error, num::NonZeroUsize, process};
+
+use aoc_common::{InputArgs, Solution};
+use clap::Parser;
+use day_1::{CalorieCounting, CalorieStats, Inventory};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+    /// List the N elves carrying the most calories
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+    /// Print descriptive statistics over every elf's inventory
+    #[arg(long)]
+    stats: bool,
+    /// Number of histogram bins printed with --stats
+    #[arg(long, value_name = "N", default_value = "10", requires = "stats")]
+    bins: NonZeroUsize,
+}
+
+fn print_stats(stats: &CalorieStats) {
+    println!("Elves: {}", stats.elves);
+    println!("Calories: min {}, median {}, mean {:.1}, max {}", stats.min, stats.median, stats.mean, stats.max);
+    let percentiles = stats.percentiles.iter().map(|(p, calories)| format!("p{p} {calories}")).collect::<Vec<_>>();
+    println!("Percentiles: {}", percentiles.join(", "));
+    let items = stats.items.iter().sum::<usize>();
+    println!("Items per elf: min {}, mean {:.1}, max {}",
+        stats.items.iter().min().unwrap_or(&0), items as f64 / stats.elves as f64, stats.items.iter().max().unwrap_or(&0));
+    let widest = stats.histogram.iter().map(|bin| bin.elves).max().unwrap_or(0).max(1);
+    println!("Histogram:");
+    for bin in &stats.histogram {
+        println!("  {:>7}-{:<7} {:<40} {}", bin.from, bin.to, "#".repeat(bin.elves * 40 / widest), bin.elves);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let reader = cli.input.source(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")).reader()
+        .map_err(|e| format!("Failed reading input: {e}"))?;
+    let inventories = day_1::parse_inventories(reader)?;
+    let grouped_calories = inventories.iter().map(Inventory::calories).collect::<Vec<_>>();
+    println!("Top elf calories: {}", CalorieCounting.part_one(&grouped_calories)?);
+    println!("Top 3 elves calories: {}", CalorieCounting.part_two(&grouped_calories)?);
+    if let Some(n) = cli.top {
+        let total = day_1::top_n_sum(&grouped_calories, n)?;
+        for (elf, calories) in day_1::top_n(grouped_calories.iter().copied(), n) {
+            println!("Elf {}: {calories}", elf + 1);
+        }
+        println!("Top {n} elves calories: {total}");
+    }
+    if cli.stats {
+        print_stats(&day_1::calorie_stats(&inventories, cli.bins)?);
+    }
+    Ok(())
+}
 
 fn main() {
-    let contents = fs::read_to_string("./src/input.txt").expect("Failed reading file");
-    let mut grouped_calories = contents.split("\n\n")
-        .map(|e| e.split('\n').fold(0u32, |acc, item| acc + item.parse::<u32>().expect("Failed parsing string")))
-        .collect::<Vec<u32>>();
-    grouped_calories.sort_by(|a, b| b.cmp(a));
-    let max_calories = grouped_calories[0];
-    let top_3_calories: u32 = grouped_calories[..3].iter().sum();
-    println!("Top elf calories: {:?}", max_calories);
-    println!("Top 3 elves calories: {:?}", top_3_calories);
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
 }
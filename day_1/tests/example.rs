@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_1::CalorieCounting;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = CalorieCounting.parse(EXAMPLE).unwrap();
+    assert_eq!(CalorieCounting.part_one(&input).unwrap(), 24000);
+}
+
+#[test]
+fn example_part_two() {
+    let input = CalorieCounting.parse(EXAMPLE).unwrap();
+    assert_eq!(CalorieCounting.part_two(&input).unwrap(), 45000);
+}
@@ -0,0 +1,192 @@
+use thiserror::Error;
+
+use crate::{Outcome, Shape};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GameError {
+    #[error("A game needs at least one shape")]
+    NoShapes,
+    #[error("A cyclic game needs an odd number of shapes, got {0}")]
+    EvenShapes(usize),
+    #[error("There is no shape {0}")]
+    UnknownShape(usize),
+    #[error("Shape {0} cannot play against itself")]
+    SelfMatchup(usize),
+    #[error("Shapes {0} and {1} are matched up more than once")]
+    DuplicateMatchup(usize, usize),
+    #[error("Shapes {0} and {1} are never matched up")]
+    MissingMatchup(usize, usize),
+}
+
+/// The shapes of a game and which of them beats which.
+///
+/// The win relation is a tournament: every two different shapes are matched up, and exactly one of them wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    names: Vec<String>,
+    beats: Vec<Vec<bool>>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Game {
+    /// Rock, paper, scissors.
+    pub fn classic() -> Self {
+        Self::cyclic(["rock", "paper", "scissors"]).expect("Three shapes form a cyclic game")
+    }
+
+    /// Rock, paper, scissors, Spock, lizard; the first three keep their classic indices.
+    pub fn rpsls() -> Self {
+        Self::cyclic(["rock", "paper", "scissors", "spock", "lizard"]).expect("Five shapes form a cyclic game")
+    }
+
+    /// Every shape beats the shapes an odd number of steps before it, wrapping around.
+    ///
+    /// With an odd number of shapes this makes each one beat exactly half of the others.
+    pub fn cyclic<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Result<Self, GameError> {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let n = names.len();
+        match n {
+            0 => return Err(GameError::NoShapes),
+            n if n % 2 == 0 => return Err(GameError::EvenShapes(n)),
+            _ => (),
+        }
+        let beats = (0..n).map(|a| (0..n).map(|b| (a + n - b) % n % 2 == 1).collect()).collect();
+        Ok(Self { names, beats })
+    }
+
+    /// Builds a game from `(winner, loser)` pairs of shape indices covering every matchup exactly once.
+    pub fn tournament<S: Into<String>>(names: impl IntoIterator<Item = S>, wins: &[(usize, usize)]) -> Result<Self, GameError> {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let n = names.len();
+        if n == 0 {
+            return Err(GameError::NoShapes);
+        }
+        let mut beats = vec![vec![false; n]; n];
+        for &(winner, loser) in wins {
+            if let Some(&shape) = [winner, loser].iter().find(|&&shape| shape >= n) {
+                return Err(GameError::UnknownShape(shape));
+            }
+            if winner == loser {
+                return Err(GameError::SelfMatchup(winner));
+            }
+            if beats[winner][loser] || beats[loser][winner] {
+                return Err(GameError::DuplicateMatchup(winner, loser));
+            }
+            beats[winner][loser] = true;
+        }
+        let mut matchups = (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b)));
+        if let Some((a, b)) = matchups.find(|&(a, b)| !beats[a][b] && !beats[b][a]) {
+            return Err(GameError::MissingMatchup(a, b));
+        }
+        Ok(Self { names, beats })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.len()).map(Shape::new)
+    }
+
+    /// Fails if `shape` is not part of the game, e.g. a shape of a bigger game.
+    pub fn check(&self, shape: Shape) -> Result<Shape, GameError> {
+        match shape.index() < self.len() {
+            true => Ok(shape),
+            false => Err(GameError::UnknownShape(shape.index())),
+        }
+    }
+
+    pub fn name(&self, shape: Shape) -> Result<&str, GameError> {
+        Ok(&self.names[self.check(shape)?.index()])
+    }
+
+    pub fn shape_named(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|n| n.eq_ignore_ascii_case(name)).map(Shape::new)
+    }
+
+    pub fn outcome(&self, me: Shape, opponent: Shape) -> Result<Outcome, GameError> {
+        let (me, opponent) = (self.check(me)?, self.check(opponent)?);
+        Ok(if me == opponent {
+            Outcome::Draw
+        } else if self.beats[me.index()][opponent.index()] {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        })
+    }
+
+    /// The shape to play against `opponent` to get `outcome`, if any.
+    ///
+    /// When several shapes qualify, the first one after `opponent` in cyclic order is picked.
+    pub fn shape_for(&self, opponent: Shape, outcome: Outcome) -> Result<Option<Shape>, GameError> {
+        let n = self.len();
+        let opponent = self.check(opponent)?;
+        Ok((0..n).map(|k| Shape::new((opponent.index() + k) % n)).find(|&me| self.outcome(me, opponent) == Ok(outcome)))
+    }
+}
+
+#[test]
+fn classic_game_test() {
+    let game = Game::classic();
+    assert_eq!(game.outcome(Shape::PAPER, Shape::ROCK), Ok(Outcome::Win));
+    assert_eq!(game.outcome(Shape::ROCK, Shape::SCISSORS), Ok(Outcome::Win));
+    assert_eq!(game.outcome(Shape::ROCK, Shape::PAPER), Ok(Outcome::Loss));
+    assert_eq!(game.outcome(Shape::SCISSORS, Shape::SCISSORS), Ok(Outcome::Draw));
+    assert_eq!(game.shape_for(Shape::ROCK, Outcome::Win), Ok(Some(Shape::PAPER)));
+    assert_eq!(game.shape_for(Shape::ROCK, Outcome::Loss), Ok(Some(Shape::SCISSORS)));
+    assert_eq!(game.shape_for(Shape::PAPER, Outcome::Draw), Ok(Some(Shape::PAPER)));
+}
+
+#[test]
+fn rpsls_game_test() {
+    let game = Game::rpsls();
+    let [rock, paper, scissors, spock, lizard] = [0, 1, 2, 3, 4].map(Shape::new);
+    for (winner, loser) in [(scissors, paper), (paper, rock), (rock, lizard), (lizard, spock), (spock, scissors),
+                            (scissors, lizard), (lizard, paper), (paper, spock), (spock, rock), (rock, scissors)] {
+        assert_eq!(game.outcome(winner, loser), Ok(Outcome::Win));
+        assert_eq!(game.outcome(loser, winner), Ok(Outcome::Loss));
+    }
+    assert_eq!(game.shape_named("Spock"), Some(spock));
+}
+
+#[test]
+fn cyclic_game_balance_test() {
+    let game = Game::cyclic((0..7).map(|i| i.to_string())).unwrap();
+    for shape in game.shapes() {
+        assert_eq!(game.shapes().filter(|&other| game.outcome(shape, other) == Ok(Outcome::Win)).count(), 3);
+        for outcome in [Outcome::Win, Outcome::Draw, Outcome::Loss] {
+            assert_eq!(game.shape_for(shape, outcome).unwrap().map(|me| game.outcome(me, shape)), Some(Ok(outcome)));
+        }
+    }
+    assert_eq!(Game::cyclic(["a", "b"]), Err(GameError::EvenShapes(2)));
+}
+
+#[test]
+fn tournament_game_test() {
+    let game = Game::tournament(["a", "b", "c"], &[(0, 1), (0, 2), (1, 2)]).unwrap();
+    assert_eq!(game.shape_for(Shape::new(0), Outcome::Win), Ok(None));
+    assert_eq!(game.shape_for(Shape::new(2), Outcome::Win), Ok(Some(Shape::new(0))));
+    assert_eq!(Game::tournament(["a", "b", "c"], &[(0, 1), (1, 2)]), Err(GameError::MissingMatchup(0, 2)));
+    assert_eq!(Game::tournament(["a", "b"], &[(0, 1), (1, 0)]), Err(GameError::DuplicateMatchup(1, 0)));
+}
+
+#[test]
+fn unknown_shape_test() {
+    let game = Game::classic();
+    let spock = Shape::new(3);
+    assert_eq!(game.outcome(spock, Shape::ROCK), Err(GameError::UnknownShape(3)));
+    assert_eq!(game.outcome(Shape::ROCK, spock), Err(GameError::UnknownShape(3)));
+    assert_eq!(game.name(spock), Err(GameError::UnknownShape(3)));
+    assert_eq!(game.shape_for(spock, Outcome::Win), Err(GameError::UnknownShape(3)));
+    assert_eq!(Game::cyclic(["rock"]).unwrap().outcome(Shape::PAPER, Shape::ROCK), Err(GameError::UnknownShape(1)));
+}
@@ -0,0 +1,321 @@
+use aoc_common::{numbered_lines, LineContext, LineError, Solution};
+use thiserror::Error;
+
+pub mod game;
+pub mod optimizer;
+pub mod replay;
+pub mod scoring;
+pub mod symbols;
+
+pub use game::{Game, GameError};
+pub use scoring::{ScoringError, ScoringRules};
+pub use symbols::{SymbolError, SymbolMap};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum StrategyError {
+    #[error("Unidentified symbol: {0}")]
+    UnknownSymbol(char),
+    #[error("Unidentified token: {0}")]
+    UnknownToken(String),
+    #[error("Expected an opponent symbol and a response symbol separated by a space")]
+    MissingColumn,
+    #[error("No shape gives a {0:?} against this opponent")]
+    NoShapeFor(Outcome),
+    #[error(transparent)]
+    Game(#[from] GameError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Play {
+    me: Shape,
+    opponent: Shape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw
+}
+
+impl Outcome {
+    pub fn from_symbol(symbol: char) -> Result<Self, StrategyError> {
+        match symbol {
+            'X' => Ok(Self::Loss),
+            'Y' => Ok(Self::Draw),
+            'Z' => Ok(Self::Win),
+            _ => Err(StrategyError::UnknownSymbol(symbol)),
+        }
+    }
+}
+
+impl Play {
+    pub fn new(me: Shape, opponent: Shape) -> Self {
+        Self { me, opponent }
+    }
+
+    pub fn me(&self) -> Shape {
+        self.me
+    }
+
+    pub fn opponent(&self) -> Shape {
+        self.opponent
+    }
+
+    fn split_columns(line: &str) -> Result<(&str, &str), StrategyError> {
+        let mut columns = line.split_whitespace();
+        match (columns.next(), columns.next()) {
+            (Some(opponent), Some(response)) => Ok((opponent, response)),
+            _ => Err(StrategyError::MissingColumn),
+        }
+    }
+
+    pub fn from_tokens_of_shape_and_result(opponent: &str, my_result: &str, game: &Game, symbols: &SymbolMap) -> Result<Self, StrategyError> {
+        let opponent = symbols.opponent_shape(opponent)?;
+        let my_result = symbols.outcome(my_result)?;
+        let me = game.shape_for(opponent, my_result)?.ok_or(StrategyError::NoShapeFor(my_result))?;
+        Ok(Self {me, opponent})
+    }
+
+    pub fn from_tokens_of_shapes(opponent: &str, me: &str, symbols: &SymbolMap) -> Result<Self, StrategyError> {
+        let opponent = symbols.opponent_shape(opponent)?;
+        let me = symbols.response_shape(me)?;
+        Ok(Self { me, opponent })
+    }
+
+    pub fn from_line_of_shape_and_result(line: &str, game: &Game, symbols: &SymbolMap) -> Result<Self, StrategyError> {
+        let (opponent, my_result) = Self::split_columns(line)?;
+        Self::from_tokens_of_shape_and_result(opponent, my_result, game, symbols)
+    }
+
+    pub fn from_line_of_shapes(line: &str, symbols: &SymbolMap) -> Result<Self, StrategyError> {
+        let (opponent, me) = Self::split_columns(line)?;
+        Self::from_tokens_of_shapes(opponent, me, symbols)
+    }
+
+    /// Fails if either shape is not part of `game`.
+    pub fn result(&self, game: &Game) -> Result<Outcome, GameError> {
+        game.outcome(self.me, self.opponent)
+    }
+
+    /// Widened to `u64`, so the configurable scores cannot overflow, neither here nor when summed over a guide.
+    pub fn score(&self, game: &Game, rules: &ScoringRules) -> Result<u64, GameError> {
+        Ok(rules.outcome_score(self.result(game)?) as u64 + rules.shape_score(self.me) as u64)
+    }
+}
+
+/// A shape by its index in a [`Game`]; the classic shapes keep the same index in every game built on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape(usize);
+
+impl Shape {
+    pub const ROCK: Shape = Shape(0);
+    pub const PAPER: Shape = Shape(1);
+    pub const SCISSORS: Shape = Shape(2);
+
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// `A`, `B`, `C`, ... name shapes in order; `X`, `Y` and `Z` also stand for the first three.
+    pub(crate) fn symbol_index(symbol: char) -> Option<usize> {
+        match symbol {
+            'X'..='Z' => Some(symbol as usize - 'X' as usize),
+            'A'..='W' => Some(symbol as usize - 'A' as usize),
+            _ => None,
+        }
+    }
+
+    /// Reads a classic rock, paper or scissors symbol.
+    pub fn from_symbol(symbol: char) -> Result<Self, StrategyError> {
+        Self::symbol_index(symbol).filter(|&i| i < 3).map(Self).ok_or(StrategyError::UnknownSymbol(symbol))
+    }
+}
+
+#[test]
+fn play_from_symbol_test() {
+    assert_eq!(Shape::from_symbol('A'), Ok(Shape::ROCK));
+    assert_eq!(Shape::from_symbol('X'), Ok(Shape::ROCK));
+    assert_eq!(Shape::from_symbol('B'), Ok(Shape::PAPER));
+    assert_eq!(Shape::from_symbol('Y'), Ok(Shape::PAPER));
+    assert_eq!(Shape::from_symbol('C'), Ok(Shape::SCISSORS));
+    assert_eq!(Shape::from_symbol('Z'), Ok(Shape::SCISSORS));
+}
+
+#[test]
+fn play_from_symbol_error_on_unknown_symbol_test() {
+    let error = Shape::from_symbol('M').unwrap_err();
+    assert_eq!(error, StrategyError::UnknownSymbol('M'));
+    assert_eq!(error.to_string(), "Unidentified symbol: M");
+}
+
+#[test]
+fn parse_reports_line_of_invalid_play_test() {
+    let error = RockPaperScissors::default().parse("A Y\nB\nC Z").unwrap_err();
+    assert_eq!(error, LineError { line: 2, text: String::from("B"), kind: StrategyError::MissingColumn });
+}
+
+/// One line of the encrypted guide, split into its two columns but not yet decoded.
+#[derive(Debug)]
+struct GuideLine {
+    line: usize,
+    text: String,
+    opponent: String,
+    response: String,
+}
+
+/// The encrypted guide as written. How its second column reads depends on the part, so each part decodes it
+/// with only the symbols it needs, see [`RockPaperScissors::plays_by_shapes`] and [`RockPaperScissors::plays_by_results`].
+#[derive(Debug)]
+pub struct StrategyGuide {
+    lines: Vec<GuideLine>,
+}
+
+impl StrategyGuide {
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RockPaperScissors {
+    game: Game,
+    rules: ScoringRules,
+    symbols: SymbolMap,
+}
+
+impl RockPaperScissors {
+    /// Reads the guide with the puzzle's symbols, see [`SymbolMap::for_game`].
+    pub fn new(game: Game, rules: ScoringRules) -> Self {
+        let symbols = SymbolMap::for_game(&game);
+        Self { game, rules, symbols }
+    }
+
+    pub fn with_symbols(self, symbols: SymbolMap) -> Self {
+        Self { symbols, ..self }
+    }
+
+    pub fn symbols(&self) -> &SymbolMap {
+        &self.symbols
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn rules(&self) -> &ScoringRules {
+        &self.rules
+    }
+
+    fn read_shapes(&self, line: &GuideLine) -> Result<Play, StrategyError> {
+        Play::from_tokens_of_shapes(&line.opponent, &line.response, &self.symbols)
+    }
+
+    fn read_results(&self, line: &GuideLine) -> Result<Play, StrategyError> {
+        Play::from_tokens_of_shape_and_result(&line.opponent, &line.response, &self.game, &self.symbols)
+    }
+
+    /// The guide decoded with its second column as my shape.
+    pub fn plays_by_shapes(&self, guide: &StrategyGuide) -> Result<Vec<Play>, LineError<StrategyError>> {
+        guide.lines.iter().map(|l| self.read_shapes(l).at_line(l.line, &l.text)).collect()
+    }
+
+    /// The guide decoded with its second column as the result I should get.
+    pub fn plays_by_results(&self, guide: &StrategyGuide) -> Result<Vec<Play>, LineError<StrategyError>> {
+        guide.lines.iter().map(|l| self.read_results(l).at_line(l.line, &l.text)).collect()
+    }
+
+    fn total_score(&self, guide: &StrategyGuide, read: impl Fn(&GuideLine) -> Result<Play, StrategyError>) -> Result<u64, LineError<StrategyError>> {
+        guide.lines.iter()
+            .map(|l| read(l).and_then(|play| Ok(play.score(&self.game, &self.rules)?)).at_line(l.line, &l.text))
+            .sum()
+    }
+}
+
+impl Solution for RockPaperScissors {
+    type Input = StrategyGuide;
+    type Answer = u64;
+    type Error = LineError<StrategyError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        let lines = numbered_lines(input).map(|(line, text)| {
+            let (opponent, response) = Play::split_columns(text).at_line(line, text)?;
+            Ok(GuideLine { line, text: text.to_owned(), opponent: opponent.to_owned(), response: response.to_owned() })
+        });
+        Ok(StrategyGuide { lines: lines.collect::<Result<_, _>>()? })
+    }
+
+    fn part_one(&self, guide: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        self.total_score(guide, |line| self.read_shapes(line))
+    }
+
+    fn part_two(&self, guide: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        self.total_score(guide, |line| self.read_results(line))
+    }
+}
+
+#[test]
+fn custom_rules_score_test() {
+    let solution = RockPaperScissors::new(Game::classic(), ScoringRules::default().with_outcome_score(Outcome::Win, 0).with_outcome_score(Outcome::Loss, 6));
+    let guide = solution.parse("A Y\nB X\nC Z").unwrap();
+    assert_eq!(solution.part_one(&guide).unwrap(), 2 + (6 + 1) + (3 + 3));
+}
+
+#[test]
+fn large_scores_test() {
+    let rules = ScoringRules::default().with_outcome_score(Outcome::Win, u32::MAX).with_shape_score(Shape::ROCK, 4_000_000_000);
+    let solution = RockPaperScissors::new(Game::classic(), rules);
+    let guide = solution.parse("A Y\nB X\nC Z").unwrap();
+    assert_eq!(solution.part_one(&guide).unwrap(), (u32::MAX as u64 + 2) + 4_000_000_000 + (3 + 3));
+    let optimization = optimizer::optimize(&solution.plays_by_shapes(&guide).unwrap(), solution.game(), solution.rules()).unwrap();
+    assert_eq!(optimization.best, (u32::MAX as u64 + 2) + (u32::MAX as u64 + 3) + (u32::MAX as u64 + 4_000_000_000));
+}
+
+#[test]
+fn rpsls_guide_test() {
+    let solution = RockPaperScissors::new(Game::rpsls(), ScoringRules::default());
+    let guide = solution.parse("D X\nE Z\nA Z").unwrap();
+    assert_eq!(solution.plays_by_shapes(&guide).unwrap().iter().map(|play| play.me().index()).collect::<Vec<_>>(), vec![0, 2, 2]);
+    assert_eq!(solution.part_one(&guide).unwrap(), 1 + (6 + 3) + 3);
+    assert_eq!(solution.plays_by_results(&guide).unwrap()[1].result(&Game::rpsls()), Ok(Outcome::Win));
+}
+
+#[test]
+fn shapes_outside_the_game_test() {
+    let play = Play::new(Shape::new(3), Shape::ROCK);
+    assert_eq!(play.score(&Game::classic(), &ScoringRules::default()), Err(GameError::UnknownShape(3)));
+    let solution = RockPaperScissors::default().with_symbols(SymbolMap::for_game(&Game::rpsls()));
+    let guide = solution.parse("A X\nD X").unwrap();
+    let error = solution.part_one(&guide).unwrap_err();
+    assert_eq!((error.line, error.kind), (2, StrategyError::Game(GameError::UnknownShape(3))));
+}
+
+#[test]
+fn custom_symbols_guide_test() {
+    let symbols = SymbolMap::parse("[opponent]\nrock = \"rock\"\npaper = \"paper\"\nscissors = \"scissors\"\n\
+                                    [response]\nrock = \"rock\"\npaper = \"paper\"\nscissors = \"scissors\"\n\
+                                    [outcome]\nrock = \"loss\"\npaper = \"draw\"\nscissors = \"win\"", &Game::classic()).unwrap();
+    let solution = RockPaperScissors::default().with_symbols(symbols);
+    let guide = solution.parse("rock paper\npaper rock\nscissors scissors").unwrap();
+    assert_eq!(solution.part_one(&guide).unwrap(), 15);
+    assert_eq!(solution.part_two(&guide).unwrap(), 12);
+}
+
+#[test]
+fn response_only_symbols_guide_test() {
+    let symbols = SymbolMap::parse("[opponent]\nrock = \"rock\"\npaper = \"paper\"\nscissors = \"scissors\"\n\
+                                    [response]\nrock = \"rock\"\npaper = \"paper\"\nscissors = \"scissors\"", &Game::classic()).unwrap();
+    let solution = RockPaperScissors::default().with_symbols(symbols);
+    let guide = solution.parse("rock paper").unwrap();
+    assert_eq!(solution.part_one(&guide).unwrap(), 8);
+    let error = solution.part_two(&guide).unwrap_err();
+    assert_eq!(error, LineError { line: 1, text: String::from("rock paper"), kind: StrategyError::UnknownToken(String::from("paper")) });
+}
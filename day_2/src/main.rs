@@ -1,163 +1,142 @@
-use std::{fs::{File}, io::{BufReader, BufRead}};
-
-struct Play {
-    me: Shape,
-    opponent: Shape,
-}
-
-enum Result {
-    Win,
-    Loss,
-    Draw
+use std::{error::Error, path::PathBuf, process};
+
+use aoc_common::{InputArgs, Solution};
+use clap::{Args, Parser, ValueEnum};
+use day_2::{replay, Game, GameError, Outcome, Play, RockPaperScissors, ScoringRules, Shape, SymbolMap};
+
+/// Scoring overrides; flags take precedence over the rules file.
+#[derive(Args)]
+struct ScoringArgs {
+    /// TOML file with scores keyed by shape name and by win, draw and loss
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+    #[arg(long, value_name = "SCORE")]
+    rock: Option<u32>,
+    #[arg(long, value_name = "SCORE")]
+    paper: Option<u32>,
+    #[arg(long, value_name = "SCORE")]
+    scissors: Option<u32>,
+    #[arg(long, value_name = "SCORE")]
+    win: Option<u32>,
+    #[arg(long, value_name = "SCORE")]
+    draw: Option<u32>,
+    #[arg(long, value_name = "SCORE")]
+    loss: Option<u32>,
 }
 
-impl Result {
-    fn score(&self) -> u32 {
-        match self {
-            Result::Win => 6,
-            Result::Loss => 0,
-            Result::Draw => 3,
+impl ScoringArgs {
+    fn rules(&self, game: &Game) -> Result<ScoringRules, Box<dyn Error>> {
+        let mut rules = match &self.rules {
+            Some(path) => ScoringRules::load(path, game)?,
+            None => ScoringRules::default(),
+        };
+        for (shape, score) in [(Shape::ROCK, self.rock), (Shape::PAPER, self.paper), (Shape::SCISSORS, self.scissors)] {
+            if let Some(score) = score {
+                rules = rules.with_shape_score(shape, score);
+            }
         }
-    }
-
-    fn from_symbol(symbol: char) -> Self {
-        match symbol {
-            'X' => Self::Loss,
-            'Y' => Self::Draw,
-            'Z' => Self::Win,
-            _ => panic!("Invalid symbol: {symbol}"),
+        for (outcome, score) in [(Outcome::Win, self.win), (Outcome::Draw, self.draw), (Outcome::Loss, self.loss)] {
+            if let Some(score) = score {
+                rules = rules.with_outcome_score(outcome, score);
+            }
         }
+        Ok(rules)
     }
 }
 
-impl Play {
-    fn from_line_of_shape_and_result(line: &str) -> Self {
-        let split_line = line.split(' ').collect::<Vec<_>>();
-        let opponent = Shape::from_symbol(split_line[0].chars().take(1).last().unwrap_or_else(|| panic!("Invalid symbol: {}", split_line[0])));
-        let my_result = Result::from_symbol(split_line[1].chars().take(1).last().unwrap_or_else(|| panic!("Invalid symbol: {}", split_line[1])));
-        let me = Shape::from_opponent_and_my_result(&opponent, &my_result);
-        Self {me, opponent}
-    }
-
-    fn from_line_of_shapes(line: &str) -> Self {
-        let split_line = line.split(' ').collect::<Vec<_>>();
-        let opponent = Shape::from_symbol(split_line[0].chars().take(1).last().unwrap_or_else(|| panic!("Invalid symbol: {}", split_line[0])));
-        let me = Shape::from_symbol(split_line[1].chars().take(1).last().unwrap_or_else(|| panic!("Invalid symbol: {}", split_line[1])));
-        Self { me, opponent }
-    }
-
-    fn result(&self) -> Result {
-        match self.me {
-            Shape::Rock => match self.opponent {
-                Shape::Rock => Result::Draw,
-                Shape::Paper => Result::Loss,
-                Shape::Scissors => Result::Win,
-            },
-            Shape::Paper => match self.opponent {
-                Shape::Rock => Result::Win,
-                Shape::Paper => Result::Draw,
-                Shape::Scissors => Result::Loss,
-            },
-            Shape::Scissors => match self.opponent {
-                Shape::Rock => Result::Loss,
-                Shape::Paper => Result::Win,
-                Shape::Scissors => Result::Draw,
-            },
-        }
-    }
-
-    fn score(&self) -> u32 {
-        self.result().score() + self.me.score()
-    }
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    scoring: ScoringArgs,
+    /// Play a cyclic game with these shapes instead, e.g. rock,paper,scissors,spock,lizard
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    shapes: Vec<String>,
+    /// TOML file with [opponent], [response] and [outcome] tables mapping guide tokens to names
+    #[arg(long, value_name = "FILE")]
+    symbols: Option<PathBuf>,
+    /// Report the best and worst possible scores, and which decoding of the response column scores highest (up to 8 shapes)
+    #[arg(long)]
+    optimize: bool,
+    /// Replay the rounds of the given part one by one, followed by per-shape results
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    replay: Option<u8>,
+    /// Output format of --replay
+    #[arg(long, value_enum, default_value_t = ReplayFormat::Text, requires = "replay")]
+    replay_format: ReplayFormat,
 }
 
-#[derive(Debug, PartialEq)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ReplayFormat {
+    Text,
+    Csv,
 }
 
-impl Shape {
-    fn from_symbol(symbol: char) -> Self {
-        match symbol {
-            'A' => Shape::Rock,
-            'X' => Shape::Rock,
-            'B' => Shape::Paper,
-            'Y' => Shape::Paper,
-            'C' => Shape::Scissors,
-            'Z' => Shape::Scissors,
-            _ => panic!("Unidentified symbol: {symbol}"),
+fn print_replay(solution: &RockPaperScissors, plays: &[Play], format: ReplayFormat) -> Result<(), Box<dyn Error>> {
+    let game = solution.game();
+    let rounds = replay::replay(plays, game, solution.rules())?;
+    if format == ReplayFormat::Csv {
+        println!("round,opponent,me,outcome,score,running_score");
+        for r in &rounds {
+            println!("{},{},{},{:?},{},{}", r.number, game.name(r.opponent)?, game.name(r.me)?, r.outcome, r.score, r.running_score);
         }
+        return Ok(());
     }
-
-    fn score(&self) -> u32 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
-        }
+    for r in &rounds {
+        println!("Round {}: {} vs {} -> {:?}, +{} = {}", r.number, game.name(r.me)?, game.name(r.opponent)?, r.outcome, r.score, r.running_score);
     }
-
-    fn from_opponent_and_my_result(opponent_shape: &Shape, result: &Result) -> Self {
-        match opponent_shape {
-            Shape::Rock => match result {
-                Result::Win => Self::Paper,
-                Result::Loss => Self::Scissors,
-                Result::Draw => Self::Rock,
-            },
-            Shape::Paper =>match result {
-                Result::Win => Self::Scissors,
-                Result::Loss => Self::Rock,
-                Result::Draw => Self::Paper,
-            },
-            Shape::Scissors =>match result {
-                Result::Win => Self::Rock,
-                Result::Loss => Self::Paper,
-                Result::Draw => Self::Scissors,
-            },
-        }
+    for t in replay::tally(&rounds, game)? {
+        println!("{}: {} rounds, {} wins, {} draws, {} losses", game.name(t.shape)?, t.rounds(), t.wins, t.draws, t.losses);
     }
+    Ok(())
 }
 
-#[test]
-fn play_from_symbol_test() {
-    assert_eq!(Shape::from_symbol('A'), Shape::Rock);
-    assert_eq!(Shape::from_symbol('X'), Shape::Rock);
-    assert_eq!(Shape::from_symbol('B'), Shape::Paper);
-    assert_eq!(Shape::from_symbol('Y'), Shape::Paper);
-    assert_eq!(Shape::from_symbol('C'), Shape::Scissors);
-    assert_eq!(Shape::from_symbol('Z'), Shape::Scissors);
-}
-
-#[test]
-#[should_panic(expected = "Unidentified symbol: M")]
-fn play_from_symbol_panic_on_unknown_symbol_test() {
-    Shape::from_symbol('M');
-}
-
-fn first_part() {
-    let file = File::open("./src/input.txt").expect("Failed reading file");
-    let reader = BufReader::new(file);
-
-    let plays: Vec<Play> = reader.lines().map(|line| Play::from_line_of_shapes(line.unwrap().as_str())).collect();
-    let score = plays.iter().fold(0, |acc, play| acc + play.score());
-
-    println!("First part; Score is: {score}");
+fn print_optimization(solution: &RockPaperScissors, plays: &[Play]) -> Result<(), Box<dyn Error>> {
+    let game = solution.game();
+    let optimization = day_2::optimizer::optimize(plays, game, solution.rules())?;
+    println!("Best possible score: {}", optimization.best);
+    println!("Worst possible score: {}", optimization.worst);
+    println!("Decodings by score:");
+    for decoding in &optimization.decodings {
+        let mapping = game.shapes().zip(&decoding.mapping)
+            .map(|(token, &shape)| Ok(format!("{}={}", solution.symbols().response_token(token).unwrap_or("?"), game.name(shape)?)))
+            .collect::<Result<Vec<_>, GameError>>()?;
+        println!("  {}: {}", mapping.join(" "), decoding.score);
+    }
+    Ok(())
 }
 
-fn second_part() {
-    let file = File::open("./src/input.txt").expect("Failed reading file");
-    let reader = BufReader::new(file);
-
-    let plays: Vec<Play> = reader.lines().map(|line| Play::from_line_of_shape_and_result(line.unwrap().as_str())).collect();
-    let score = plays.iter().fold(0, |acc, play| acc + play.score());
-
-    println!("Second part; Score is: {score}");
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let input = cli.input.source(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")).read()
+        .map_err(|e| format!("Failed reading input: {e}"))?;
+    let game = match cli.shapes.is_empty() {
+        true => Game::classic(),
+        false => Game::cyclic(cli.shapes)?,
+    };
+    let rules = cli.scoring.rules(&game)?;
+    let symbols = match &cli.symbols {
+        Some(path) => SymbolMap::load(path, &game)?,
+        None => SymbolMap::for_game(&game),
+    };
+    let solution = RockPaperScissors::new(game, rules).with_symbols(symbols);
+    let guide = solution.parse(&input)?;
+    println!("First part; Score is: {}", solution.part_one(&guide)?);
+    println!("Second part; Score is: {}", solution.part_two(&guide)?);
+    if cli.optimize {
+        print_optimization(&solution, &solution.plays_by_shapes(&guide)?)?;
+    }
+    match cli.replay {
+        Some(1) => print_replay(&solution, &solution.plays_by_shapes(&guide)?, cli.replay_format)?,
+        Some(_) => print_replay(&solution, &solution.plays_by_results(&guide)?, cli.replay_format)?,
+        None => (),
+    }
+    Ok(())
 }
 
 fn main() {
-    first_part();
-    second_part();
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
 }
-
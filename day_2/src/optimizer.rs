@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{Game, GameError, Play, ScoringRules, Shape};
+
+/// Games with more shapes have too many decodings to list; 8 shapes already have 40320.
+pub const MAX_SHAPES: usize = 8;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum OptimizeError {
+    #[error("Cannot list the decodings of a game with {0} shapes, at most {MAX_SHAPES} are supported")]
+    TooManyShapes(usize),
+    #[error(transparent)]
+    Game(#[from] GameError),
+}
+
+/// One way of decoding the response column: the symbol read as shape `i` is played as `mapping[i]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoding {
+    pub mapping: Vec<Shape>,
+    pub score: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimization {
+    /// Total when every round is answered with the highest scoring shape.
+    pub best: u64,
+    /// Total when every round is answered with the lowest scoring shape.
+    pub worst: u64,
+    /// Every permutation of the response symbols, highest score first. Ties keep lexicographic order.
+    pub decodings: Vec<Decoding>,
+}
+
+/// Calls `visit` with every ordering of `0..n`, generated in place with Heap's algorithm.
+fn for_each_permutation(n: usize, mut visit: impl FnMut(&[usize])) {
+    let mut permutation = (0..n).collect::<Vec<_>>();
+    let mut counters = vec![0; n];
+    visit(&permutation);
+    let mut i = 1;
+    while i < n {
+        if counters[i] < i {
+            permutation.swap(if i % 2 == 0 { 0 } else { counters[i] }, i);
+            visit(&permutation);
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+/// Scores every possible answer to the opponent column of `plays`, which are read with [`Play::from_line_of_shapes`].
+pub fn optimize(plays: &[Play], game: &Game, rules: &ScoringRules) -> Result<Optimization, OptimizeError> {
+    if game.len() > MAX_SHAPES {
+        return Err(OptimizeError::TooManyShapes(game.len()));
+    }
+    let mut rounds: HashMap<(Shape, Shape), u64> = HashMap::new();
+    for play in plays {
+        play.result(game)?;
+        *rounds.entry((play.opponent(), play.me())).or_default() += 1;
+    }
+    // `scores[me][opponent]` for every matchup of the game.
+    let scores = game.shapes()
+        .map(|me| game.shapes().map(|opponent| Play::new(me, opponent).score(game, rules)).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let against = |opponent: Shape| scores.iter().map(move |row| row[opponent.index()]);
+    let best = plays.iter().map(|play| against(play.opponent()).max().unwrap_or(0)).sum();
+    let worst = plays.iter().map(|play| against(play.opponent()).min().unwrap_or(0)).sum();
+
+    let mut decodings = vec![];
+    for_each_permutation(game.len(), |permutation| {
+        let score = rounds.iter()
+            .map(|(&(opponent, me), &count)| count * scores[permutation[me.index()]][opponent.index()])
+            .sum();
+        decodings.push(Decoding { mapping: permutation.iter().copied().map(Shape::new).collect(), score });
+    });
+    let indices = |decoding: &Decoding| decoding.mapping.iter().map(Shape::index).collect::<Vec<_>>();
+    decodings.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| indices(a).cmp(&indices(b))));
+    Ok(Optimization { best, worst, decodings })
+}
+
+#[test]
+fn for_each_permutation_test() {
+    let permutations = |n| {
+        let mut permutations = vec![];
+        for_each_permutation(n, |permutation| permutations.push(permutation.to_vec()));
+        permutations.sort();
+        permutations
+    };
+    assert_eq!(permutations(3), vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 0, 2], vec![1, 2, 0], vec![2, 0, 1], vec![2, 1, 0]]);
+    let mut five = permutations(5);
+    five.dedup();
+    assert_eq!(five.len(), 120);
+    assert_eq!(permutations(0), vec![Vec::<usize>::new()]);
+}
+
+#[test]
+fn optimize_test() {
+    let game = Game::classic();
+    let rules = ScoringRules::default();
+    let symbols = crate::SymbolMap::for_game(&game);
+    let plays = ["A Y", "B X", "C Z"].map(|line| Play::from_line_of_shapes(line, &symbols).unwrap());
+    let optimization = optimize(&plays, &game, &rules).unwrap();
+    assert_eq!(optimization.best, 8 + 9 + 7);
+    assert_eq!(optimization.worst, 3 + 1 + 2);
+    assert_eq!(optimization.decodings.len(), 6);
+    assert_eq!(optimization.decodings[0], Decoding { mapping: vec![Shape::SCISSORS, Shape::PAPER, Shape::ROCK], score: 24 });
+    let identity = optimization.decodings.iter().find(|d| d.mapping == [Shape::ROCK, Shape::PAPER, Shape::SCISSORS]).unwrap();
+    assert_eq!(identity.score, 15);
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i"].map(String::from).to_vec();
+    assert_eq!(optimize(&plays, &Game::cyclic(names).unwrap(), &rules), Err(OptimizeError::TooManyShapes(9)));
+    let spock = Play::new(Shape::new(3), Shape::ROCK);
+    assert_eq!(optimize(&[spock], &game, &rules), Err(OptimizeError::Game(GameError::UnknownShape(3))));
+}
@@ -0,0 +1,72 @@
+use crate::{Game, GameError, Outcome, Play, ScoringRules, Shape};
+
+/// A single round of the tournament as it was played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Round {
+    /// 1-based, matching the guide's line numbers.
+    pub number: usize,
+    pub opponent: Shape,
+    pub me: Shape,
+    pub outcome: Outcome,
+    pub score: u64,
+    /// Total of this round and every round before it.
+    pub running_score: u64,
+}
+
+/// How the rounds in which I played `shape` turned out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeTally {
+    pub shape: Shape,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl ShapeTally {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+}
+
+pub fn replay(plays: &[Play], game: &Game, rules: &ScoringRules) -> Result<Vec<Round>, GameError> {
+    let mut running_score = 0;
+    plays.iter().enumerate().map(|(i, play)| {
+        let score = play.score(game, rules)?;
+        running_score += score;
+        Ok(Round { number: i + 1, opponent: play.opponent(), me: play.me(), outcome: play.result(game)?, score, running_score })
+    }).collect()
+}
+
+/// One tally per shape of the game, in shape order, including shapes that were never played.
+pub fn tally(rounds: &[Round], game: &Game) -> Result<Vec<ShapeTally>, GameError> {
+    let mut tallies = game.shapes().map(|shape| ShapeTally { shape, wins: 0, draws: 0, losses: 0 }).collect::<Vec<_>>();
+    for round in rounds {
+        let tally = &mut tallies[game.check(round.me)?.index()];
+        match round.outcome {
+            Outcome::Win => tally.wins += 1,
+            Outcome::Draw => tally.draws += 1,
+            Outcome::Loss => tally.losses += 1,
+        }
+    }
+    Ok(tallies)
+}
+
+#[test]
+fn replay_test() {
+    let game = Game::classic();
+    let plays = [Play::new(Shape::PAPER, Shape::ROCK), Play::new(Shape::ROCK, Shape::PAPER), Play::new(Shape::SCISSORS, Shape::SCISSORS)];
+    let rounds = replay(&plays, &game, &ScoringRules::default()).unwrap();
+    assert_eq!(rounds.iter().map(|r| r.outcome).collect::<Vec<_>>(), vec![Outcome::Win, Outcome::Loss, Outcome::Draw]);
+    assert_eq!(rounds.iter().map(|r| (r.score, r.running_score)).collect::<Vec<_>>(), vec![(8, 8), (1, 9), (6, 15)]);
+    assert_eq!(rounds[2], Round { number: 3, opponent: Shape::SCISSORS, me: Shape::SCISSORS, outcome: Outcome::Draw, score: 6, running_score: 15 });
+}
+
+#[test]
+fn tally_test() {
+    let game = Game::classic();
+    let plays = [Play::new(Shape::PAPER, Shape::ROCK), Play::new(Shape::PAPER, Shape::SCISSORS), Play::new(Shape::ROCK, Shape::ROCK)];
+    let tallies = tally(&replay(&plays, &game, &ScoringRules::default()).unwrap(), &game).unwrap();
+    assert_eq!(tallies[0], ShapeTally { shape: Shape::ROCK, wins: 0, draws: 1, losses: 0 });
+    assert_eq!(tallies[1], ShapeTally { shape: Shape::PAPER, wins: 1, draws: 0, losses: 1 });
+    assert_eq!(tallies[2].rounds(), 0);
+}
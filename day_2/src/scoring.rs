@@ -0,0 +1,110 @@
+use std::{fs, path::Path};
+
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::{Game, Outcome, Shape};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ScoringError {
+    #[error("Failed reading scoring rules: {0}")]
+    Unreadable(String),
+    #[error("Invalid scoring rules: {0}")]
+    InvalidToml(String),
+    #[error("Unknown scoring rule `{0}`")]
+    UnknownRule(String),
+    #[error("Score for `{0}` must be a non-negative integer")]
+    InvalidScore(String),
+}
+
+const OUTCOME_KEYS: [&str; 3] = ["win", "draw", "loss"];
+
+/// Points awarded per round: one score for the shape I played plus one for the outcome.
+///
+/// Unless overridden, the shape at index `i` scores `i + 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoringRules {
+    shapes: Vec<u32>,
+    outcomes: [u32; 3],
+}
+
+impl Default for ScoringRules {
+    /// The tournament rules from the puzzle: 1/2/3 for rock/paper/scissors, 6/3/0 for win/draw/loss.
+    fn default() -> Self {
+        Self { shapes: vec![], outcomes: [6, 3, 0] }
+    }
+}
+
+fn outcome_index(outcome: Outcome) -> usize {
+    match outcome {
+        Outcome::Win => 0,
+        Outcome::Draw => 1,
+        Outcome::Loss => 2,
+    }
+}
+
+impl ScoringRules {
+    pub fn shape_score(&self, shape: Shape) -> u32 {
+        self.shapes.get(shape.index()).copied().unwrap_or(shape.index() as u32 + 1)
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        self.outcomes[outcome_index(outcome)]
+    }
+
+    pub fn with_shape_score(mut self, shape: Shape, score: u32) -> Self {
+        while self.shapes.len() <= shape.index() {
+            self.shapes.push(self.shapes.len() as u32 + 1);
+        }
+        self.shapes[shape.index()] = score;
+        self
+    }
+
+    pub fn with_outcome_score(mut self, outcome: Outcome, score: u32) -> Self {
+        self.outcomes[outcome_index(outcome)] = score;
+        self
+    }
+
+    /// Reads scores keyed by the game's shape names and by `win`, `draw` and `loss` from a TOML table.
+    /// Missing keys keep their defaults.
+    pub fn parse(contents: &str, game: &Game) -> Result<Self, ScoringError> {
+        let table: Table = contents.parse().map_err(|e: toml::de::Error| ScoringError::InvalidToml(e.message().to_owned()))?;
+        let mut rules = Self::default();
+        for (key, value) in &table {
+            let score = match value {
+                Value::Integer(score) => u32::try_from(*score).map_err(|_| ScoringError::InvalidScore(key.clone()))?,
+                _ => return Err(ScoringError::InvalidScore(key.clone())),
+            };
+            if let Some(shape) = game.shape_named(key) {
+                rules = rules.with_shape_score(shape, score);
+            } else if let Some(i) = OUTCOME_KEYS.iter().position(|k| k == key) {
+                rules.outcomes[i] = score;
+            } else {
+                return Err(ScoringError::UnknownRule(key.clone()));
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn load(path: &Path, game: &Game) -> Result<Self, ScoringError> {
+        let contents = fs::read_to_string(path).map_err(|e| ScoringError::Unreadable(format!("{}: {e}", path.display())))?;
+        Self::parse(&contents, game)
+    }
+}
+
+#[test]
+fn parse_scoring_rules_test() {
+    let game = Game::classic();
+    let rules = ScoringRules::parse("rock = 10\nwin = 1\nloss = 2", &game).unwrap();
+    assert_eq!(rules.shape_score(Shape::ROCK), 10);
+    assert_eq!(rules.shape_score(Shape::PAPER), 2);
+    assert_eq!(rules.outcome_score(Outcome::Win), 1);
+    assert_eq!(rules.outcome_score(Outcome::Draw), 3);
+    assert_eq!(rules.outcome_score(Outcome::Loss), 2);
+    assert_eq!(ScoringRules::parse("", &game).unwrap(), ScoringRules::default());
+    assert_eq!(ScoringRules::parse("lizard = 4", &game), Err(ScoringError::UnknownRule(String::from("lizard"))));
+    assert_eq!(ScoringRules::parse("draw = -1", &game), Err(ScoringError::InvalidScore(String::from("draw"))));
+    let rules = ScoringRules::parse("lizard = 9", &Game::rpsls()).unwrap();
+    assert_eq!(rules.shape_score(Shape::new(4)), 9);
+    assert_eq!(rules.shape_score(Shape::new(3)), 4);
+}
@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::{Game, Outcome, Shape, StrategyError};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SymbolError {
+    #[error("Failed reading symbol mapping: {0}")]
+    Unreadable(String),
+    #[error("Invalid symbol mapping: {0}")]
+    InvalidToml(String),
+    #[error("Unknown symbol mapping section `{0}`, expected opponent, response or outcome")]
+    UnknownSection(String),
+    #[error("Symbol `{0}` must map to a shape or outcome name")]
+    InvalidEntry(String),
+    #[error("There is no shape named `{0}`")]
+    UnknownShape(String),
+    #[error("There is no outcome named `{0}`")]
+    UnknownOutcome(String),
+}
+
+/// How the tokens in each column of a strategy guide are read.
+///
+/// The first column always names the opponent's shape. The second names my shape in part one and the
+/// desired outcome in part two, so it has a table for each reading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMap {
+    opponent: BTreeMap<String, Shape>,
+    response: BTreeMap<String, Shape>,
+    outcomes: BTreeMap<String, Outcome>,
+}
+
+impl Default for SymbolMap {
+    fn default() -> Self {
+        Self::for_game(&Game::classic())
+    }
+}
+
+fn outcome_named(name: &str) -> Option<Outcome> {
+    match name.to_ascii_lowercase().as_str() {
+        "win" => Some(Outcome::Win),
+        "draw" => Some(Outcome::Draw),
+        "loss" => Some(Outcome::Loss),
+        _ => None,
+    }
+}
+
+impl SymbolMap {
+    /// The puzzle's encoding: see [`Shape::from_symbol`] and [`Outcome::from_symbol`].
+    pub fn for_game(game: &Game) -> Self {
+        let shapes = ('A'..='Z')
+            .filter_map(|symbol| Some((symbol.to_string(), Shape::new(Shape::symbol_index(symbol)?))))
+            .filter(|(_, shape)| shape.index() < game.len())
+            .collect::<BTreeMap<_, _>>();
+        let outcomes = ['X', 'Y', 'Z'].into_iter()
+            .filter_map(|symbol| Some((symbol.to_string(), Outcome::from_symbol(symbol).ok()?)))
+            .collect();
+        Self { opponent: shapes.clone(), response: shapes, outcomes }
+    }
+
+    pub fn with_opponent(mut self, token: &str, shape: Shape) -> Self {
+        self.opponent.insert(token.to_owned(), shape);
+        self
+    }
+
+    pub fn with_response(mut self, token: &str, shape: Shape) -> Self {
+        self.response.insert(token.to_owned(), shape);
+        self
+    }
+
+    pub fn with_outcome(mut self, token: &str, outcome: Outcome) -> Self {
+        self.outcomes.insert(token.to_owned(), outcome);
+        self
+    }
+
+    pub fn opponent_shape(&self, token: &str) -> Result<Shape, StrategyError> {
+        self.opponent.get(token).copied().ok_or_else(|| StrategyError::UnknownToken(token.to_owned()))
+    }
+
+    pub fn response_shape(&self, token: &str) -> Result<Shape, StrategyError> {
+        self.response.get(token).copied().ok_or_else(|| StrategyError::UnknownToken(token.to_owned()))
+    }
+
+    pub fn outcome(&self, token: &str) -> Result<Outcome, StrategyError> {
+        self.outcomes.get(token).copied().ok_or_else(|| StrategyError::UnknownToken(token.to_owned()))
+    }
+
+    /// The token that labels `shape` in the response column: `X`, `Y` or `Z` if one reads as it, as in the
+    /// puzzle's encoding, and otherwise the first such token in sorted order.
+    pub fn response_token(&self, shape: Shape) -> Option<&str> {
+        let tokens = || self.response.iter().filter(move |(_, &s)| s == shape).map(|(token, _)| token.as_str());
+        tokens().find(|token| matches!(*token, "X" | "Y" | "Z")).or_else(|| tokens().next())
+    }
+
+    /// Reads `[opponent]`, `[response]` and `[outcome]` tables mapping tokens to shape or outcome names.
+    ///
+    /// A table that is present replaces that column's default symbols entirely, e.g.
+    /// `[opponent]` with `rock = "rock"` and `paper = "paper"` accepts only those two tokens.
+    pub fn parse(contents: &str, game: &Game) -> Result<Self, SymbolError> {
+        let table: Table = contents.parse().map_err(|e: toml::de::Error| SymbolError::InvalidToml(e.message().to_owned()))?;
+        let mut symbols = Self::for_game(game);
+        for (section, entries) in &table {
+            let entries = entries.as_table().ok_or_else(|| SymbolError::InvalidEntry(section.clone()))?;
+            let names = entries.iter()
+                .map(|(token, name)| match name {
+                    Value::String(name) => Ok((token.clone(), name.as_str())),
+                    _ => Err(SymbolError::InvalidEntry(token.clone())),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let shapes = || names.iter()
+                .map(|(token, name)| Ok((token.clone(), game.shape_named(name).ok_or_else(|| SymbolError::UnknownShape(name.to_string()))?)))
+                .collect::<Result<BTreeMap<_, _>, SymbolError>>();
+            match section.as_str() {
+                "opponent" => symbols.opponent = shapes()?,
+                "response" => symbols.response = shapes()?,
+                "outcome" => symbols.outcomes = names.iter()
+                    .map(|(token, name)| Ok((token.clone(), outcome_named(name).ok_or_else(|| SymbolError::UnknownOutcome(name.to_string()))?)))
+                    .collect::<Result<_, SymbolError>>()?,
+                _ => return Err(SymbolError::UnknownSection(section.clone())),
+            }
+        }
+        Ok(symbols)
+    }
+
+    pub fn load(path: &Path, game: &Game) -> Result<Self, SymbolError> {
+        let contents = fs::read_to_string(path).map_err(|e| SymbolError::Unreadable(format!("{}: {e}", path.display())))?;
+        Self::parse(&contents, game)
+    }
+}
+
+#[test]
+fn default_symbols_test() {
+    let symbols = SymbolMap::default();
+    assert_eq!(symbols.opponent_shape("B"), Ok(Shape::PAPER));
+    assert_eq!(symbols.response_shape("Z"), Ok(Shape::SCISSORS));
+    assert_eq!(symbols.outcome("X"), Ok(Outcome::Loss));
+    assert_eq!(symbols.opponent_shape("D"), Err(StrategyError::UnknownToken(String::from("D"))));
+    assert_eq!(SymbolMap::for_game(&Game::rpsls()).opponent_shape("E"), Ok(Shape::new(4)));
+}
+
+#[test]
+fn response_token_test() {
+    fn labels<'a>(symbols: &'a SymbolMap, game: &Game) -> Vec<Option<&'a str>> {
+        game.shapes().map(|shape| symbols.response_token(shape)).collect()
+    }
+    assert_eq!(labels(&SymbolMap::default(), &Game::classic()), vec![Some("X"), Some("Y"), Some("Z")]);
+    let rpsls = Game::rpsls();
+    assert_eq!(labels(&SymbolMap::for_game(&rpsls), &rpsls), vec![Some("X"), Some("Y"), Some("Z"), Some("D"), Some("E")]);
+    let symbols = SymbolMap::parse("[response]\nrock = \"rock\"\nstone = \"rock\"", &Game::classic()).unwrap();
+    assert_eq!(labels(&symbols, &Game::classic()), vec![Some("rock"), None, None]);
+}
+
+#[test]
+fn parse_symbols_test() {
+    let symbols = SymbolMap::parse("[opponent]\nrock = \"rock\"\npaper = \"Paper\"\n[outcome]\nlose = \"loss\"", &Game::classic()).unwrap();
+    assert_eq!(symbols.opponent_shape("paper"), Ok(Shape::PAPER));
+    assert!(symbols.opponent_shape("A").is_err());
+    assert_eq!(symbols.response_shape("X"), Ok(Shape::ROCK));
+    assert_eq!(symbols.outcome("lose"), Ok(Outcome::Loss));
+    assert_eq!(SymbolMap::parse("[opponent]\nA = \"spock\"", &Game::classic()), Err(SymbolError::UnknownShape(String::from("spock"))));
+    assert_eq!(SymbolMap::parse("[moves]\nA = \"rock\"", &Game::classic()), Err(SymbolError::UnknownSection(String::from("moves"))));
+}
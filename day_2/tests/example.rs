@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_2::RockPaperScissors;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = RockPaperScissors::default().parse(EXAMPLE).unwrap();
+    assert_eq!(RockPaperScissors::default().part_one(&input).unwrap(), 15);
+}
+
+#[test]
+fn example_part_two() {
+    let input = RockPaperScissors::default().parse(EXAMPLE).unwrap();
+    assert_eq!(RockPaperScissors::default().part_two(&input).unwrap(), 12);
+}
@@ -0,0 +1,35 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_3::{find_shared_character, Rucksack};
+
+const INPUT: &str = include_str!("../src/input.txt");
+
+/// Compares the `HashMap` based search with bitmask intersection on the real input.
+fn shared_items(c: &mut Criterion) {
+    let lines = INPUT.lines().collect::<Vec<_>>();
+    let compartments = lines.iter().map(|line| line.split_at(line.len() / 2)).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("day_3/shared_items");
+    group.bench_function("compartments/hash_map", |b| b.iter(|| {
+        black_box(&compartments).iter()
+            .map(|&(first, second)| find_shared_character(&[first, second]))
+            .collect::<Vec<_>>()
+    }));
+    group.bench_function("compartments/bitmask", |b| b.iter(|| {
+        black_box(&compartments).iter()
+            .map(|(first, second)| first.parse::<Rucksack>().unwrap().intersection(&second.parse().unwrap()))
+            .collect::<Vec<_>>()
+    }));
+    group.bench_function("groups/hash_map", |b| b.iter(|| {
+        black_box(&lines).chunks(3).map(find_shared_character).collect::<Vec<_>>()
+    }));
+    group.bench_function("groups/bitmask", |b| b.iter(|| {
+        black_box(&lines).chunks(3)
+            .map(|chunk| chunk.iter().fold(Rucksack::ALL, |shared, line| shared.intersection(&line.parse().unwrap())))
+            .collect::<Vec<_>>()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, shared_items);
+criterion_main!(benches);
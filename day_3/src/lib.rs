@@ -0,0 +1,396 @@
+use std::{collections::HashMap, num::NonZeroUsize, str::FromStr};
+
+use aoc_common::{numbered_lines, LineContext, LineError, Solution};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum RucksackError {
+    #[error("Invalid non-ASCII character: {0}")]
+    NonAscii(char),
+    #[error("Character is non alphabetic: {0}")]
+    NonAlphabetic(char),
+    #[error("No item is shared by both compartments")]
+    NoSharedItem,
+    #[error("Both compartments share more than one item: {0}")]
+    MultipleSharedItems(String),
+    #[error("No badge is shared by the group starting here")]
+    NoBadge,
+    #[error("The group starting here shares more than one item: {0}")]
+    MultipleBadges(String),
+    #[error("Odd number of items ({0}) cannot be split into two compartments")]
+    OddLength(usize),
+    #[error("The last group has {found} rucksacks instead of {expected}")]
+    IncompleteGroup { found: usize, expected: usize },
+}
+
+/// Item priority: `a`-`z` map to 1-26 and `A`-`Z` to 27-52.
+pub fn char_to_priority(c: char) -> Result<u32, RucksackError> {
+    if !c.is_ascii() {
+        return Err(RucksackError::NonAscii(c));
+    }
+    if c.is_uppercase() {
+        Ok((c as u8 - 38) as u32)
+    } else if c.is_lowercase() {
+        Ok((c as u8 - 96) as u32)
+    } else {
+        Err(RucksackError::NonAlphabetic(c))
+    }
+}
+
+#[test]
+fn char_to_priority_test() {
+    assert_eq!(char_to_priority('p'), Ok(16));
+    assert_eq!(char_to_priority('L'), Ok(38));
+    assert_eq!(char_to_priority('P'), Ok(42));
+    assert_eq!(char_to_priority('v'), Ok(22));
+    assert_eq!(char_to_priority('t'), Ok(20));
+    assert_eq!(char_to_priority('s'), Ok(19));
+    assert_eq!(char_to_priority('1'), Err(RucksackError::NonAlphabetic('1')));
+    assert_eq!(char_to_priority('é'), Err(RucksackError::NonAscii('é')));
+}
+
+fn priority_to_char(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+/// The item types in a rucksack, or compartment, as a bitmask: bit `n` is set when the item of priority `n` is present.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rucksack(u64);
+
+impl Rucksack {
+    /// Every item type, the identity for [`Rucksack::intersection`].
+    pub const ALL: Rucksack = Rucksack(((1 << 53) - 1) & !1);
+
+    /// Item types in priority order.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        let mask = self.0;
+        (1..=52).filter(move |&priority| mask & (1 << priority) != 0).map(priority_to_char)
+    }
+
+    pub fn intersection(&self, other: &Rucksack) -> Rucksack {
+        Rucksack(self.0 & other.0)
+    }
+
+    /// Sum of the priorities of every item type, each counted once.
+    pub fn priority_sum(&self) -> u32 {
+        (1..=52).filter(|&priority| self.0 & (1 << priority) != 0).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = RucksackError;
+
+    fn from_str(items: &str) -> Result<Self, Self::Err> {
+        items.chars().try_fold(Rucksack::default(), |rucksack, c| Ok(Rucksack(rucksack.0 | 1 << char_to_priority(c)?)))
+    }
+}
+
+#[test]
+fn rucksack_test() {
+    let first: Rucksack = "vJrwpWtwJgWr".parse().unwrap();
+    let second: Rucksack = "hcsFMMfFFhFp".parse().unwrap();
+    assert_eq!(first.items().collect::<String>(), "gprtvwJW");
+    assert_eq!(first.intersection(&second).items().collect::<Vec<_>>(), vec!['p']);
+    assert_eq!(first.intersection(&second).priority_sum(), 16);
+    assert_eq!("aAzZ".parse::<Rucksack>().unwrap().priority_sum(), 1 + 27 + 26 + 52);
+    assert!("".parse::<Rucksack>().unwrap().is_empty());
+    assert_eq!(Rucksack::ALL.len(), 52);
+    assert_eq!("ab1".parse::<Rucksack>(), Err(RucksackError::NonAlphabetic('1')));
+}
+
+/// Every item type found in all of `rucksacks`.
+pub fn common_items<S: AsRef<str>>(rucksacks: &[S]) -> Result<Rucksack, RucksackError> {
+    rucksacks.iter().try_fold(Rucksack::ALL, |common, rucksack| Ok(common.intersection(&rucksack.as_ref().parse()?)))
+}
+
+#[test]
+fn common_items_test() {
+    let group = ["vJrwpWtwJgWrhcsFMMfFFhFp", "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", "PmmdzqPrVvPwwTWBwg"];
+    assert_eq!(common_items(&group).unwrap().items().collect::<String>(), "r");
+    assert_eq!(common_items(&group[..2]).unwrap().items().collect::<String>(), "frsFM");
+    assert_eq!(common_items(&["ab", "cd"]).unwrap(), Rucksack::default());
+}
+
+/// The items shared by one group of consecutive rucksacks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupBadges {
+    /// Line of the group's first rucksack.
+    pub line: usize,
+    pub badges: Rucksack,
+}
+
+/// Splits `backpacks` into groups of `group_size` and finds the items each group shares; the last group may be smaller.
+pub fn group_badges(backpacks: &[String], group_size: NonZeroUsize) -> Result<Vec<GroupBadges>, LineError<RucksackError>> {
+    backpacks.chunks(group_size.get()).enumerate().map(|(i, chunk)| {
+        let line = i * group_size.get() + 1;
+        Ok(GroupBadges { line, badges: common_items(chunk).at_line(line, &chunk[0])? })
+    }).collect()
+}
+
+/// Sum of badge priorities over groups of `group_size`. Every group must be complete and share exactly one item.
+pub fn badge_sum(backpacks: &[String], group_size: NonZeroUsize) -> Result<u32, LineError<RucksackError>> {
+    let found = backpacks.len() % group_size;
+    if found != 0 {
+        let line = backpacks.len() - found + 1;
+        return Err(RucksackError::IncompleteGroup { found, expected: group_size.get() }).at_line(line, &backpacks[line - 1]);
+    }
+    let mut badge_sum = 0u32;
+    for group in group_badges(backpacks, group_size)? {
+        match group.badges.len() {
+            0 => return Err(RucksackError::NoBadge).at_line(group.line, &backpacks[group.line - 1]),
+            1 => badge_sum += group.badges.priority_sum(),
+            _ => return Err(RucksackError::MultipleBadges(group.badges.items().collect())).at_line(group.line, &backpacks[group.line - 1]),
+        }
+    }
+    Ok(badge_sum)
+}
+
+#[test]
+fn badge_sum_test() {
+    let backpacks = ["abcX", "bcdX", "cdeX", "Xyz", "Xqc"].map(String::from);
+    let size = |n| NonZeroUsize::new(n).unwrap();
+    assert_eq!(badge_sum(&backpacks[..3], size(3)).unwrap_err().kind, RucksackError::MultipleBadges(String::from("cX")));
+    assert_eq!(badge_sum(&backpacks[3..], size(2)), Ok(char_to_priority('X').unwrap()));
+    assert_eq!(badge_sum(&backpacks[2..], size(1)).unwrap_err().kind, RucksackError::MultipleBadges(String::from("cdeX")));
+    let groups = group_badges(&backpacks, size(2)).unwrap();
+    assert_eq!(groups.iter().map(|g| (g.line, g.badges.len())).collect::<Vec<_>>(), vec![(1, 3), (3, 1), (5, 3)]);
+    let error = badge_sum(&backpacks, size(3)).unwrap_err();
+    assert_eq!((error.line, error.kind), (4, RucksackError::IncompleteGroup { found: 2, expected: 3 }));
+    let error = badge_sum(&backpacks, size(2)).unwrap_err();
+    assert_eq!((error.line, error.kind), (5, RucksackError::IncompleteGroup { found: 1, expected: 2 }));
+}
+
+/// Returns the first character of the last string that appears in every one of `strings`.
+pub fn find_shared_character(strings: &[&str]) -> Option<char> {
+    let mut chars = HashMap::new();
+
+    for (i, string) in strings.iter().enumerate() {
+        if i == strings.len() - 1 {
+            for c in string.chars() {
+                if let Some(&existing) = chars.get(&c) {
+                    if i - 1 == existing {return Some(c)}
+                }
+            }
+        } else {
+            for c in string.chars() {
+                match chars.get(&c) {
+                    Some(&existing) => if i == 0 || i - 1 == existing {chars.insert(c, i);},
+                    None => if i == 0 {chars.insert(c, i);},
+                }
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn find_shared_character_test() {
+    assert_eq!(find_shared_character(&["vJrwpWtwJgWr", "hcsFMMfFFhFp"]), Some('p'));
+    assert_eq!(find_shared_character(&["vJrwWtwJgWr", "hcsFMMfFFhF"]), None);
+    assert_eq!(find_shared_character(&["jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"]), Some('L'));
+    assert_eq!(find_shared_character(&["jqHRNqRjqzjGDG", "rsFMfFZSrrFZsS"]), None);
+    assert_eq!(find_shared_character(&["vJrwpWtwJgWrhcsFMMfFFhFp", "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", "PmmdzqPrVvPwwTWBwg"]), Some('r'));
+}
+
+pub struct RucksackReorganization;
+
+impl Solution for RucksackReorganization {
+    type Input = Vec<String>;
+    type Answer = u32;
+    type Error = LineError<RucksackError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        numbered_lines(input).map(|(line, text)| {
+            for c in text.chars() {
+                char_to_priority(c).at_line(line, text)?;
+            }
+            Ok(String::from(text))
+        }).collect()
+    }
+
+    fn part_one(&self, backpacks: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        let mut priority_sum = 0u32;
+        for (i, line) in backpacks.iter().enumerate() {
+            if line.len() % 2 == 1 {
+                return Err(RucksackError::OddLength(line.len())).at_line(i + 1, line);
+            }
+            let (first_half, second_half) = line.split_at(line.len() / 2);
+            let first_half = first_half.parse::<Rucksack>().at_line(i + 1, line)?;
+            let shared = first_half.intersection(&second_half.parse().at_line(i + 1, line)?);
+            if let Some(error) = shared_item_error(&shared) {
+                return Err(error).at_line(i + 1, line);
+            }
+            priority_sum += shared.priority_sum();
+        }
+        Ok(priority_sum)
+    }
+
+    fn part_two(&self, backpacks: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        badge_sum(backpacks, NonZeroUsize::new(3).unwrap())
+    }
+}
+
+/// Why a rucksack whose compartments share `shared` was packed wrong, if it was: exactly one item type belongs in both.
+fn shared_item_error(shared: &Rucksack) -> Option<RucksackError> {
+    match shared.len() {
+        0 => Some(RucksackError::NoSharedItem),
+        1 => None,
+        _ => Some(RucksackError::MultipleSharedItems(shared.items().collect())),
+    }
+}
+
+#[test]
+fn part_one_requires_a_single_shared_item_test() {
+    let backpacks = RucksackReorganization.parse("abab").unwrap();
+    let error = RucksackReorganization.part_one(&backpacks).unwrap_err();
+    assert_eq!(error.kind, RucksackError::MultipleSharedItems(String::from("ab")));
+    let backpacks = RucksackReorganization.parse("abcb").unwrap();
+    assert_eq!(RucksackReorganization.part_one(&backpacks), Ok(2));
+}
+
+#[test]
+fn parse_reports_non_alphabetic_item_test() {
+    let error = RucksackReorganization.parse("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqz1GDLGLrsFMfFZSrLrFZsSL").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.kind, RucksackError::NonAlphabetic('1'));
+}
+
+/// Every problem in `input` that would make either part fail or give a wrong answer, in line order.
+///
+/// Reports the first invalid item of a line, odd-length lines, lines whose compartments share no item or
+/// more than one, and a final group smaller than `group_size`.
+pub fn validate(input: &str, group_size: NonZeroUsize) -> Vec<LineError<RucksackError>> {
+    let mut issues = vec![];
+    let mut lines = 0;
+    for (line, text) in numbered_lines(input) {
+        lines = line;
+        let shared_item_error = || {
+            let (first_half, second_half) = text.split_at(text.len() / 2);
+            common_items(&[first_half, second_half]).ok().as_ref().and_then(shared_item_error)
+        };
+        let kind = if let Some(error) = text.chars().find_map(|c| char_to_priority(c).err()) {
+            error
+        } else if text.len() % 2 == 1 {
+            RucksackError::OddLength(text.len())
+        } else if let Some(error) = shared_item_error() {
+            error
+        } else {
+            continue;
+        };
+        issues.push(LineError { line, text: text.to_owned(), kind });
+    }
+    let found = lines % group_size;
+    if found != 0 {
+        let line = lines - found + 1;
+        let text = input.lines().nth(line - 1).unwrap_or_default().to_owned();
+        issues.push(LineError { line, text, kind: RucksackError::IncompleteGroup { found, expected: group_size.get() } });
+    }
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+#[test]
+fn validate_test() {
+    let size = |n| NonZeroUsize::new(n).unwrap();
+    let issues = validate("abca\nab1b\nabc\nabcd\né\n", size(3));
+    assert_eq!(issues.iter().map(|issue| (issue.line, issue.kind.clone())).collect::<Vec<_>>(), vec![
+        (2, RucksackError::NonAlphabetic('1')),
+        (3, RucksackError::OddLength(3)),
+        (4, RucksackError::NoSharedItem),
+        (4, RucksackError::IncompleteGroup { found: 2, expected: 3 }),
+        (5, RucksackError::NonAscii('é')),
+    ]);
+    assert!(validate("vJrwpWtwJgWrhcsFMMfFFhFp\n", size(1)).is_empty());
+    let issues = validate("abab\n", size(1));
+    assert_eq!(issues.iter().map(|issue| (issue.line, issue.kind.clone())).collect::<Vec<_>>(), vec![
+        (1, RucksackError::MultipleSharedItems(String::from("ab"))),
+    ]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compartment {
+    First,
+    Second,
+}
+
+/// Moving `count` copies of `item` out of `from` into the other compartment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemMove {
+    pub item: char,
+    pub count: usize,
+    pub from: Compartment,
+}
+
+/// The moves that leave no item type in both compartments of one rucksack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalancePlan {
+    pub line: usize,
+    pub moves: Vec<ItemMove>,
+}
+
+impl RebalancePlan {
+    pub fn total_moves(&self) -> usize {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+}
+
+/// Fewest single-item moves so that every item type ends up in one compartment, in priority order.
+///
+/// Each shared type moves to the compartment already holding more of it, or to the first on a tie.
+/// Compartment sizes are allowed to change.
+pub fn plan_rebalance(rucksack: &str) -> Result<Vec<ItemMove>, RucksackError> {
+    // Checking every item first also guarantees the line is ASCII, so it splits on a character boundary.
+    let priorities = rucksack.chars().map(char_to_priority).collect::<Result<Vec<_>, _>>()?;
+    if priorities.len() % 2 == 1 {
+        return Err(RucksackError::OddLength(priorities.len()));
+    }
+    let (first_half, second_half) = priorities.split_at(priorities.len() / 2);
+    let mut counts = [[0usize; 53]; 2];
+    for (compartment, half) in [first_half, second_half].into_iter().enumerate() {
+        for &priority in half {
+            counts[compartment][priority as usize] += 1;
+        }
+    }
+    Ok((1..=52u32).filter_map(|priority| {
+        let (first, second) = (counts[0][priority as usize], counts[1][priority as usize]);
+        let item = priority_to_char(priority);
+        match (first, second) {
+            (0, _) | (_, 0) => None,
+            _ if first >= second => Some(ItemMove { item, count: second, from: Compartment::Second }),
+            _ => Some(ItemMove { item, count: first, from: Compartment::First }),
+        }
+    }).collect())
+}
+
+pub fn plan_rebalances(backpacks: &[String]) -> Result<Vec<RebalancePlan>, LineError<RucksackError>> {
+    backpacks.iter().enumerate()
+        .map(|(i, backpack)| Ok(RebalancePlan { line: i + 1, moves: plan_rebalance(backpack).at_line(i + 1, backpack)? }))
+        .collect()
+}
+
+#[test]
+fn plan_rebalance_test() {
+    assert_eq!(plan_rebalance("vJrwpWtwJgWrhcsFMMfFFhFp"), Ok(vec![ItemMove { item: 'p', count: 1, from: Compartment::Second }]));
+    assert_eq!(plan_rebalance("aabBbbaB"), Ok(vec![
+        ItemMove { item: 'a', count: 1, from: Compartment::Second },
+        ItemMove { item: 'b', count: 1, from: Compartment::First },
+        ItemMove { item: 'B', count: 1, from: Compartment::Second },
+    ]));
+    assert_eq!(plan_rebalance("abcd"), Ok(vec![]));
+    assert_eq!(plan_rebalance("abc"), Err(RucksackError::OddLength(3)));
+    assert_eq!(plan_rebalance("aéa"), Err(RucksackError::NonAscii('é')));
+    assert_eq!(plan_rebalance("ab1b"), Err(RucksackError::NonAlphabetic('1')));
+    let plans = plan_rebalances(&["aabBbbaB", "abab"].map(String::from)).unwrap();
+    assert_eq!(plans.iter().map(RebalancePlan::total_moves).collect::<Vec<_>>(), vec![3, 2]);
+}
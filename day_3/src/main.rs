@@ -1,84 +1,73 @@
-use std::{fs::{File, self}, io::{BufReader, BufRead}, collections::HashMap};
+use std::{error::Error, num::NonZeroUsize, process};
 
-fn char_to_priority(c: char) -> u32 {
-    if !c.is_ascii() {
-        panic!("Invalid non-ASCII character: {c}");
-    }
-    if c.is_uppercase() {
-        (c as u8 - 38) as u32
-    } else if c.is_lowercase() {
-        (c as u8 - 96) as u32
-    } else {
-        panic!("Character is non alphabetic: {c}");
-    }
-}
+use aoc_common::{InputArgs, Solution};
+use clap::Parser;
+use day_3::{Compartment, RebalancePlan, RucksackReorganization};
 
-#[test]
-fn char_to_priority_test() {
-    assert_eq!(char_to_priority('p'), 16);
-    assert_eq!(char_to_priority('L'), 38);
-    assert_eq!(char_to_priority('P'), 42);
-    assert_eq!(char_to_priority('v'), 22);
-    assert_eq!(char_to_priority('t'), 20);
-    assert_eq!(char_to_priority('s'), 19);
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Number of elves per group when looking for badges
+    #[arg(long, value_name = "N", default_value = "3")]
+    group_size: NonZeroUsize,
+    /// List the groups that share no item or more than one item
+    #[arg(long)]
+    badge_report: bool,
+    /// Report every malformed line before solving
+    #[arg(long)]
+    validate: bool,
+    /// Plan the item moves that leave no item type in both compartments
+    #[arg(long)]
+    rebalance: bool,
 }
 
-fn find_shared_character(strings: &[&str]) -> Option<char> {
-    let mut chars = HashMap::new();
+fn print_rebalance(plans: &[RebalancePlan]) {
+    for plan in plans.iter().filter(|plan| !plan.moves.is_empty()) {
+        let moves = plan.moves.iter()
+            .map(|m| format!("{} x {} to the {} compartment", m.count, m.item, match m.from {
+                Compartment::First => "second",
+                Compartment::Second => "first",
+            }))
+            .collect::<Vec<_>>();
+        println!("Line {}: {}", plan.line, moves.join(", "));
+    }
+    println!("Total moves: {}", plans.iter().map(RebalancePlan::total_moves).sum::<usize>());
+}
 
-    for (i, string) in strings.iter().enumerate() {
-        if i == strings.len() - 1 {
-            for c in string.chars() {
-                if let Some(&existing) = chars.get(&c) {
-                    if i - 1 == existing {return Some(c)}
-                }
-            }
-        } else {
-            for c in string.chars() {
-                match chars.get(&c) {
-                    Some(&existing) => if i == 0 || i - 1 == existing {chars.insert(c, i);},
-                    None => if i == 0 {chars.insert(c, i);},
-                }
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let input = cli.input.source(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")).read()
+        .map_err(|e| format!("Failed reading input: {e}"))?;
+    if cli.validate {
+        let issues = day_3::validate(&input, cli.group_size);
+        for issue in &issues {
+            println!("{issue}");
+        }
+        if !issues.is_empty() {
+            return Err(format!("Found {} problem(s) in the input", issues.len()).into());
+        }
+        println!("Input is valid");
+    }
+    let backpacks = RucksackReorganization.parse(&input)?;
+    println!("Sum of all priorities is: {}", RucksackReorganization.part_one(&backpacks)?);
+    if cli.badge_report {
+        for group in day_3::group_badges(&backpacks, cli.group_size)?.iter().filter(|g| g.badges.len() != 1) {
+            match group.badges.len() {
+                0 => println!("Group starting on line {} shares no item", group.line),
+                _ => println!("Group starting on line {} shares {} items: {}", group.line, group.badges.len(), group.badges.items().collect::<String>()),
             }
         }
     }
-    None
-}
-
-#[test]
-fn find_shared_character_test() {
-    assert_eq!(find_shared_character(&["vJrwpWtwJgWr", "hcsFMMfFFhFp"]), Some('p'));
-    assert_eq!(find_shared_character(&["vJrwWtwJgWr", "hcsFMMfFFhF"]), None);
-    assert_eq!(find_shared_character(&["jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"]), Some('L'));
-    assert_eq!(find_shared_character(&["jqHRNqRjqzjGDG", "rsFMfFZSrrFZsS"]), None);
-    assert_eq!(find_shared_character(&["vJrwpWtwJgWrhcsFMMfFFhFp", "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", "PmmdzqPrVvPwwTWBwg"]), Some('r'));
-}
-
-fn first_part() {
-    let file = File::open("./src/input.txt").expect("Failed reading file");
-    let reader = BufReader::new(file);
-    let priority_sum = reader.lines().fold(0u32, |acc, line| {
-        let line = line.unwrap();
-        let (first_half, second_half) = line.split_at(line.len() / 2);
-        let shared_character = find_shared_character(&[first_half, second_half]);
-        acc + char_to_priority(shared_character.unwrap())
-    });
-
-    println!("Sum of all priorities is: {priority_sum}");
-}
-
-fn second_part() {
-    let contents = fs::read_to_string("./src/input.txt").expect("Failed reading file");
-    let backpacks = contents.split('\n').collect::<Vec<_>>();
-    let badge_sum = backpacks.chunks(3).fold(0u32, |acc, chunk| {
-        let badge = find_shared_character(chunk).expect("Failed to find shared character in chunk");
-        acc + char_to_priority(badge)
-    });
-
-    println!("Sum of priorities of badges is: {badge_sum}");
+    println!("Sum of priorities of badges is: {}", day_3::badge_sum(&backpacks, cli.group_size)?);
+    if cli.rebalance {
+        print_rebalance(&day_3::plan_rebalances(&backpacks)?);
+    }
+    Ok(())
 }
 
 fn main() {
-    first_part();
-    second_part();
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_3::RucksackReorganization;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = RucksackReorganization.parse(EXAMPLE).unwrap();
+    assert_eq!(RucksackReorganization.part_one(&input).unwrap(), 157);
+}
+
+#[test]
+fn example_part_two() {
+    let input = RucksackReorganization.parse(EXAMPLE).unwrap();
+    assert_eq!(RucksackReorganization.part_two(&input).unwrap(), 70);
+}
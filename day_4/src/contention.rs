@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{AssignmentGroup, SectionRange, SectionSet};
+
+/// Whose assignment a range is: the 1-based input line and the range's position within that line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Owner {
+    pub line: usize,
+    pub elf: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contention {
+    /// Most elves cleaning one section at the same time.
+    pub max_elves: usize,
+    /// Every section cleaned by `max_elves` elves.
+    pub busiest: SectionSet,
+    /// Every two assignments from different lines that share a section, the smaller owner first.
+    ///
+    /// Pairs come in the order the sweep finds them: by where the later of the two ranges starts, ties broken by
+    /// its owner, and then by the owner of the range it overlaps.
+    pub overlaps: Vec<(Owner, Owner)>,
+}
+
+/// Sweeps over the assignments of every line at once.
+///
+/// Runs in O(n log n + k), where k is the number of overlapping pairs from different lines.
+pub fn analyze_contention(groups: &[AssignmentGroup]) -> Contention {
+    // Ends are exclusive, so they can sit one past `u32::MAX`; at equal positions they sort before starts.
+    let mut events = groups.iter().enumerate()
+        .flat_map(|(i, group)| group.ranges().iter().enumerate().map(move |(elf, &range)| (Owner { line: i + 1, elf }, range)))
+        .filter(|(_, range)| !range.is_empty())
+        .flat_map(|(owner, range)| [(range.from() as u64, true, owner), (range.to() as u64 + 1, false, owner)])
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+
+    // Active owners by line, so a starting range only visits lines that each give it at least one pair.
+    let mut active: BTreeMap<usize, BTreeSet<Owner>> = BTreeMap::new();
+    let mut active_elves = 0;
+    let mut overlaps = vec![];
+    let mut max_elves = 0;
+    let mut busiest = vec![];
+    for (i, &(position, is_start, owner)) in events.iter().enumerate() {
+        if is_start {
+            overlaps.extend(active.iter()
+                .filter(|&(&line, _)| line != owner.line)
+                .flat_map(|(_, others)| others.iter().map(|&other| (other.min(owner), other.max(owner)))));
+            active.entry(owner.line).or_default().insert(owner);
+            active_elves += 1;
+        } else if let Some(owners) = active.get_mut(&owner.line) {
+            owners.remove(&owner);
+            if owners.is_empty() {
+                active.remove(&owner.line);
+            }
+            active_elves -= 1;
+        }
+        let next = match events.get(i + 1) {
+            Some(&(next, _, _)) if next == position => continue,
+            Some(&(next, _, _)) => next,
+            None => break,
+        };
+        if active_elves > max_elves {
+            max_elves = active_elves;
+            busiest.clear();
+        }
+        if active_elves == max_elves && max_elves > 0 {
+            busiest.push(SectionRange::new(position as u32, (next - 1) as u32));
+        }
+    }
+    Contention { max_elves, busiest: busiest.into_iter().collect(), overlaps }
+}
+
+#[test]
+fn analyze_contention_test() {
+    let groups = ["2-4,6-8", "2-3,4-5", "5-7,7-9", "1-1,10-10"].map(|line| line.parse::<AssignmentGroup>().unwrap());
+    let contention = analyze_contention(&groups);
+    assert_eq!(contention.max_elves, 3);
+    assert_eq!(contention.busiest, [SectionRange::new(7, 7)].into_iter().collect());
+    let owner = |line, elf| Owner { line, elf };
+    assert_eq!(contention.overlaps, vec![
+        (owner(1, 0), owner(2, 0)),
+        (owner(1, 0), owner(2, 1)),
+        (owner(2, 1), owner(3, 0)),
+        (owner(1, 1), owner(3, 0)),
+        (owner(1, 1), owner(3, 1)),
+    ]);
+}
+
+#[test]
+fn analyze_contention_edges_test() {
+    let groups = ["0-4294967295", "4294967295-4294967295,0-0"].map(|line| line.parse::<AssignmentGroup>().unwrap());
+    let contention = analyze_contention(&groups);
+    assert_eq!(contention.max_elves, 2);
+    assert_eq!(contention.busiest.ranges(), &[SectionRange::new(0, 0), SectionRange::new(u32::MAX, u32::MAX)]);
+    assert_eq!(contention.overlaps.len(), 2);
+    assert_eq!(analyze_contention(&[]), Contention { max_elves: 0, busiest: SectionSet::new(), overlaps: vec![] });
+}
+
+#[test]
+fn analyze_contention_single_line_test() {
+    let group = (1..=200).map(|i| format!("{i}-1000")).collect::<Vec<_>>().join(",").parse::<AssignmentGroup>().unwrap();
+    let contention = analyze_contention(&[group]);
+    assert_eq!(contention.max_elves, 200);
+    assert_eq!(contention.busiest, SectionRange::new(200, 1000).into());
+    assert!(contention.overlaps.is_empty());
+}
@@ -0,0 +1,286 @@
+use std::str::FromStr;
+
+use aoc_common::{numbered_lines, LineContext, LineError, Solution};
+use thiserror::Error;
+
+pub mod contention;
+pub mod section_set;
+
+pub use contention::{analyze_contention, Contention, Owner};
+pub use section_set::SectionSet;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SectionError {
+    #[error("Expected a section range like `2-4` at column {column}, found `{text}`")]
+    MalformedRange { column: usize, text: String },
+    #[error("Missing section number at column {column}")]
+    MissingSection { column: usize },
+    #[error("Invalid section number `{text}` at column {column}")]
+    InvalidSection { column: usize, text: String },
+    #[error("Section range `{from}-{to}` at column {column} ends before it starts")]
+    InvertedRange { column: usize, from: u32, to: u32 },
+    #[error("Expected two comma separated section ranges")]
+    MalformedPair,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Pair {
+    first: SectionRange,
+    second: SectionRange,
+}
+
+impl Pair {
+    pub fn new(first: SectionRange, second: SectionRange) -> Self {
+        Self { first, second }
+    }
+
+    pub fn first(&self) -> &SectionRange {
+        &self.first
+    }
+
+    pub fn second(&self) -> &SectionRange {
+        &self.second
+    }
+}
+
+impl FromStr for Pair {
+    type Err = SectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.split(',').count() != 2 {
+            return Err(SectionError::MalformedPair);
+        }
+        let group = s.parse::<AssignmentGroup>()?;
+        Ok(Self { first: group.ranges[0], second: group.ranges[1] })
+    }
+}
+
+/// The section ranges assigned to one group of elves, one line of the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssignmentGroup {
+    ranges: Vec<SectionRange>,
+}
+
+impl AssignmentGroup {
+    pub fn new(ranges: Vec<SectionRange>) -> Self {
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[SectionRange] {
+        &self.ranges
+    }
+
+    /// Whether any two ranges of the group share a section.
+    pub fn any_overlap(&self) -> bool {
+        let mut ranges = self.ranges.iter().filter(|r| !r.is_empty()).collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.from);
+        ranges.windows(2).scan(0, |reach, w| {
+            *reach = (*reach).max(w[0].to);
+            Some(w[1].from <= *reach)
+        }).any(|overlap| overlap)
+    }
+
+    /// Index of a range that fully contains every other range of the group, the first one if several do.
+    pub fn container(&self) -> Option<usize> {
+        let from = self.ranges.iter().map(|r| r.from).min()?;
+        let to = self.ranges.iter().map(|r| r.to).max()?;
+        self.ranges.iter().position(|r| r.from == from && r.to == to)
+    }
+
+    /// Sections assigned to every elf of the group.
+    pub fn common_intersection(&self) -> Option<SectionRange> {
+        let (first, rest) = self.ranges.split_first()?;
+        rest.iter().try_fold(*first, |common, range| common.intersection(range))
+    }
+}
+
+impl From<Pair> for AssignmentGroup {
+    fn from(pair: Pair) -> Self {
+        Self { ranges: vec![pair.first, pair.second] }
+    }
+}
+
+impl FromStr for AssignmentGroup {
+    type Err = SectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut column = 1;
+        let ranges = s.split(',').map(|range| {
+            let parsed = parse_range(range, column);
+            column += range.len() + 1;
+            parsed
+        });
+        Ok(Self { ranges: ranges.collect::<Result<_, _>>()? })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectionRange {
+    from: u32,
+    to: u32,
+}
+
+impl SectionRange {
+    /// Does not check the bounds; an inverted range is empty. Other crates build ranges with
+    /// [`SectionRange::checked`] or [`SectionRange::normalized`] instead.
+    pub(crate) fn new(from: u32, to: u32) -> Self {
+        Self { from, to }
+    }
+
+    /// `None` if the range would end before it starts.
+    pub fn checked(from: u32, to: u32) -> Option<Self> {
+        (from <= to).then_some(Self { from, to })
+    }
+
+    /// The range between two sections given in either order.
+    pub fn normalized(a: u32, b: u32) -> Self {
+        Self { from: a.min(b), to: a.max(b) }
+    }
+
+    pub fn from(&self) -> u32 {
+        self.from
+    }
+
+    pub fn to(&self) -> u32 {
+        self.to
+    }
+
+    /// Number of sections in the range; an inverted range is empty.
+    pub fn len(&self) -> u64 {
+        match self.from <= self.to {
+            true => (self.to - self.from) as u64 + 1,
+            false => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.from > self.to
+    }
+
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        let range = SectionRange::new(self.from.max(other.from), self.to.min(other.to));
+        (!range.is_empty()).then_some(range)
+    }
+}
+
+/// Parses a range that starts at the 1-based `column` of its line, so errors can point into the line.
+fn parse_range(s: &str, column: usize) -> Result<SectionRange, SectionError> {
+    let (from, to) = s.split_once('-').ok_or_else(|| SectionError::MalformedRange { column, text: s.to_owned() })?;
+    let parse_section = |section: &str, column| match section {
+        "" => Err(SectionError::MissingSection { column }),
+        _ => section.parse::<u32>().map_err(|_| SectionError::InvalidSection { column, text: section.to_owned() }),
+    };
+    let (from, to) = (parse_section(from, column)?, parse_section(to, column + from.len() + 1)?);
+    SectionRange::checked(from, to).ok_or(SectionError::InvertedRange { column, from, to })
+}
+
+impl FromStr for SectionRange {
+    type Err = SectionError;
+
+    /// Rejects missing and inverted bounds; columns in errors count from the start of `s`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_range(s, 1)
+    }
+}
+
+pub fn is_fully_contained_in(needle: &SectionRange, haystack: &SectionRange) -> bool {
+    needle.from >= haystack.from && needle.to <= haystack.to
+}
+
+pub fn are_overlapping(a: &SectionRange, b: &SectionRange) -> bool {
+    a.from >= b.from && a.from <= b.to || b.from >= a.from && b.from <= a.to
+}
+
+#[test]
+fn is_fully_contained_in_test() {
+    assert!(is_fully_contained_in(&SectionRange {from: 2, to: 4}, &SectionRange { from: 1, to: 8 }));
+    assert!(!is_fully_contained_in(&SectionRange {from: 2, to: 4}, &SectionRange { from: 6, to: 8 }));
+}
+
+#[test]
+fn are_overlapping_test() {
+    assert!(are_overlapping(&SectionRange {from: 2, to: 4}, &SectionRange { from: 1, to: 5 }));
+    assert!(are_overlapping(&SectionRange {from: 1, to: 5}, &SectionRange { from: 2, to: 4 }));
+    assert!(are_overlapping(&SectionRange {from: 1, to: 3}, &SectionRange { from: 2, to: 4 }));
+    assert!(are_overlapping(&SectionRange {from: 3, to: 7}, &SectionRange { from: 2, to: 4 }));
+    assert!(are_overlapping(&SectionRange {from: 2, to: 4}, &SectionRange { from: 3, to: 7 }));
+}
+
+#[test]
+fn section_range_intersection_test() {
+    assert_eq!(SectionRange::new(2, 6).intersection(&SectionRange::new(4, 8)), Some(SectionRange::new(4, 6)));
+    assert_eq!(SectionRange::new(2, 3).intersection(&SectionRange::new(4, 8)), None);
+    assert_eq!(SectionRange::new(2, 6).len(), 5);
+    assert_eq!(SectionRange::new(6, 2).len(), 0);
+}
+
+#[test]
+fn section_range_from_str_test() {
+    assert_eq!("2-4".parse::<SectionRange>(), Ok(SectionRange {from: 2, to: 4}));
+    assert_eq!("2".parse::<SectionRange>(), Err(SectionError::MalformedRange { column: 1, text: String::from("2") }));
+    assert_eq!("2-x".parse::<SectionRange>(), Err(SectionError::InvalidSection { column: 3, text: String::from("x") }));
+    assert_eq!("-4".parse::<SectionRange>(), Err(SectionError::MissingSection { column: 1 }));
+    assert_eq!("12-".parse::<SectionRange>(), Err(SectionError::MissingSection { column: 4 }));
+    assert_eq!("4-2".parse::<SectionRange>(), Err(SectionError::InvertedRange { column: 1, from: 4, to: 2 }));
+    assert_eq!("3-3".parse::<SectionRange>(), Ok(SectionRange::new(3, 3)));
+    assert_eq!("2-4".parse::<Pair>(), Err(SectionError::MalformedPair));
+    assert_eq!("1-2,3-4,5-6".parse::<Pair>(), Err(SectionError::MalformedPair));
+    assert_eq!("1-2,3-4".parse::<Pair>(), Ok(Pair::new(SectionRange::new(1, 2), SectionRange::new(3, 4))));
+    assert_eq!("2-4,6-".parse::<Pair>(), Err(SectionError::MissingSection { column: 7 }));
+    assert_eq!("20-40,8-6".parse::<Pair>(), Err(SectionError::InvertedRange { column: 7, from: 8, to: 6 }));
+}
+
+#[test]
+fn section_range_constructors_test() {
+    assert_eq!(SectionRange::checked(2, 4), Some(SectionRange::new(2, 4)));
+    assert_eq!(SectionRange::checked(4, 2), None);
+    assert_eq!(SectionRange::normalized(4, 2), SectionRange::new(2, 4));
+}
+
+#[test]
+fn parse_error_position_test() {
+    let error = CampCleanup.parse("2-4,6-8\n2-3,4-5,9-x").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.kind, SectionError::InvalidSection { column: 11, text: String::from("x") });
+    assert_eq!(error.to_string(), "line 2: Invalid section number `x` at column 11 (`2-3,4-5,9-x`)");
+}
+
+#[test]
+fn assignment_group_test() {
+    let group = "2-8,3-7,4-5".parse::<AssignmentGroup>().unwrap();
+    assert!(group.any_overlap());
+    assert_eq!(group.container(), Some(0));
+    assert_eq!(group.common_intersection(), Some(SectionRange::new(4, 5)));
+
+    let group = "1-2,5-6,3-4,7-9".parse::<AssignmentGroup>().unwrap();
+    assert!(!group.any_overlap());
+    assert_eq!(group.container(), None);
+    assert_eq!(group.common_intersection(), None);
+
+    let group = "1-3,7-9,2-2".parse::<AssignmentGroup>().unwrap();
+    assert!(group.any_overlap());
+    assert_eq!("1-3,4-9,1-9".parse::<AssignmentGroup>().unwrap().container(), Some(2));
+    assert_eq!("5-5".parse::<AssignmentGroup>().unwrap().container(), Some(0));
+    assert_eq!("1-2,x".parse::<AssignmentGroup>(), Err(SectionError::MalformedRange { column: 5, text: String::from("x") }));
+}
+
+/// Counts groups where one elf's assignment contains everyone else's (part one) and where any two overlap (part two).
+pub struct CampCleanup;
+
+impl Solution for CampCleanup {
+    type Input = Vec<AssignmentGroup>;
+    type Answer = u32;
+    type Error = LineError<SectionError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        numbered_lines(input).map(|(line, text)| text.parse::<AssignmentGroup>().at_line(line, text)).collect()
+    }
+
+    fn part_one(&self, groups: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        Ok(groups.iter().filter(|group| group.container().is_some()).count() as u32)
+    }
+
+    fn part_two(&self, groups: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        Ok(groups.iter().filter(|group| group.any_overlap()).count() as u32)
+    }
+}
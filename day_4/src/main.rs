@@ -1,91 +1,79 @@
-use std::{io::{BufReader, BufRead}, fs::File};
+use std::{error::Error, process};
 
-struct Pair {
-    first: SectionRange,
-    second: SectionRange,
-}
+use aoc_common::{InputArgs, Solution};
+use clap::Parser;
+use day_4::{analyze_contention, AssignmentGroup, CampCleanup, SectionSet};
 
-impl FromIterator<SectionRange> for Pair {
-    fn from_iter<T: IntoIterator<Item = SectionRange>>(iter: T) -> Self {
-        let mut into_iter = iter.into_iter();
-        let first = into_iter.next().expect("Cannot construct Pair from an empty iterator.");
-        let second = into_iter.next().expect("Cannot construct Pair from an empty iterator.");
-        Self {first, second}
-    }
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Report how many sections are cleaned and which ones nobody cleans
+    #[arg(long)]
+    coverage: bool,
+    /// Print the sections every elf of each line cleans
+    #[arg(long)]
+    common: bool,
+    /// Report the busiest sections across all lines and how many assignments from different lines overlap
+    #[arg(long)]
+    contention: bool,
+    /// With --contention, also list every overlapping pair of assignments from different lines
+    #[arg(long, requires = "contention")]
+    list_overlaps: bool,
 }
 
-struct SectionRange {
-    from: u32,
-    to: u32,
-}
-
-impl FromIterator<u32> for SectionRange {
-    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
-        let mut into_iter = iter.into_iter();
-        let from = into_iter.next().expect("Cannot construct SectionRange from an empty iterator");
-        let to = into_iter.next().expect("Cannot construct SectionRange from an empty iterator");
-        Self {from, to}
+fn print_contention(groups: &[AssignmentGroup], list_overlaps: bool) {
+    let contention = analyze_contention(groups);
+    let busiest = contention.busiest.ranges().iter().map(|r| format!("{}-{}", r.from(), r.to())).collect::<Vec<_>>();
+    println!("At most {} elves clean the same section, in sections {}", contention.max_elves, busiest.join(", "));
+    println!("{} pairs of assignments from different lines overlap", contention.overlaps.len());
+    if list_overlaps {
+        for (a, b) in &contention.overlaps {
+            println!("  line {} elf {} and line {} elf {}", a.line, a.elf + 1, b.line, b.elf + 1);
+        }
     }
 }
 
-fn is_fully_contained_in(needle: &SectionRange, haystack: &SectionRange) -> bool {
-    needle.from >= haystack.from && needle.to <= haystack.to
-}
-
-fn are_overlapping(a: &SectionRange, b: &SectionRange) -> bool {
-    a.from >= b.from && a.from <= b.to || b.from >= a.from && b.from <= a.to
-}
-
-#[test]
-fn is_fully_contained_in_test() {
-    assert!(is_fully_contained_in(&SectionRange {from: 2, to: 4}, &SectionRange { from: 1, to: 8 }));
-    assert!(!is_fully_contained_in(&SectionRange {from: 2, to: 4}, &SectionRange { from: 6, to: 8 }));
-}
-
-#[test]
-fn are_overlapping_test() {
-    assert!(are_overlapping(&SectionRange {from: 2, to: 4}, &SectionRange { from: 1, to: 5 }));
-    assert!(are_overlapping(&SectionRange {from: 1, to: 5}, &SectionRange { from: 2, to: 4 }));
-    assert!(are_overlapping(&SectionRange {from: 1, to: 3}, &SectionRange { from: 2, to: 4 }));
-    assert!(are_overlapping(&SectionRange {from: 3, to: 7}, &SectionRange { from: 2, to: 4 }));
-    assert!(are_overlapping(&SectionRange {from: 2, to: 4}, &SectionRange { from: 3, to: 7 }));
-}
-
-fn first_part() {
-    let file = File::open("./src/input.txt").expect("Failed reading file");
-    let reader = BufReader::new(file);
-    let result = reader.lines().fold(0u32, |acc, line| {
-        let pair = line.unwrap().split(',').map(|section_range| {
-            section_range.split('-').map(|section_part| section_part.parse::<u32>().unwrap()).collect::<SectionRange>()
-        }).collect::<Pair>();
-        if is_fully_contained_in(&pair.first, &pair.second) || is_fully_contained_in(&pair.second, &pair.first) {
-            acc + 1
-        } else {
-            acc
+fn print_coverage(groups: &[AssignmentGroup]) {
+    let cleaned = groups.iter().flat_map(|group| group.ranges().iter().copied()).collect::<SectionSet>();
+    println!("{} sections are cleaned", cleaned.len());
+    if let Some(bounds) = cleaned.bounds() {
+        let gaps = cleaned.gaps(bounds);
+        if gaps.is_empty() {
+            println!("Every section between {} and {} is cleaned", bounds.from(), bounds.to());
+            return;
         }
-    });
-
-    println!("{result} pairs have fully overlaping sections");
+        let ranges = gaps.ranges().iter().map(|r| format!("{}-{}", r.from(), r.to())).collect::<Vec<_>>();
+        println!("{} sections between {} and {} are cleaned by nobody: {}", gaps.len(), bounds.from(), bounds.to(), ranges.join(", "));
+    }
 }
 
-fn second_part() {
-    let file = File::open("./src/input.txt").expect("Failed reading file");
-    let reader = BufReader::new(file);
-    let result = reader.lines().fold(0u32, |acc, line| {
-        let pair = line.unwrap().split(',').map(|section_range| {
-            section_range.split('-').map(|section_part| section_part.parse::<u32>().unwrap()).collect::<SectionRange>()
-        }).collect::<Pair>();
-        if are_overlapping(&pair.first, &pair.second) {
-            acc + 1
-        } else {
-            acc
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let input = cli.input.source(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")).read()
+        .map_err(|e| format!("Failed reading input: {e}"))?;
+    let groups = CampCleanup.parse(&input)?;
+    println!("{} pairs have fully overlaping sections", CampCleanup.part_one(&groups)?);
+    println!("{} pairs have overlaping sections", CampCleanup.part_two(&groups)?);
+    if cli.common {
+        for (i, group) in groups.iter().enumerate() {
+            match group.common_intersection() {
+                Some(common) => println!("Line {}: every elf cleans {}-{}", i + 1, common.from(), common.to()),
+                None => println!("Line {}: no section is shared by every elf", i + 1),
+            }
         }
-    });
-
-    println!("{result} pairs have overlaping sections");
+    }
+    if cli.coverage {
+        print_coverage(&groups);
+    }
+    if cli.contention {
+        print_contention(&groups, cli.list_overlaps);
+    }
+    Ok(())
 }
 
 fn main() {
-    first_part();
-    second_part();
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("Error: {error}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,154 @@
+use crate::SectionRange;
+
+/// A set of sections, stored as sorted ranges that neither overlap nor touch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SectionSet {
+    ranges: Vec<SectionRange>,
+}
+
+/// Sorts `ranges` and merges the ones that overlap or touch. Inverted ranges are empty and dropped.
+pub fn merge(ranges: impl IntoIterator<Item = SectionRange>) -> Vec<SectionRange> {
+    let mut ranges = ranges.into_iter().filter(|r| r.from() <= r.to()).collect::<Vec<_>>();
+    ranges.sort_by_key(|r| (r.from(), r.to()));
+    let mut merged: Vec<SectionRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.from() <= last.to().saturating_add(1) => *last = SectionRange::new(last.from(), last.to().max(range.to())),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl FromIterator<SectionRange> for SectionSet {
+    fn from_iter<I: IntoIterator<Item = SectionRange>>(ranges: I) -> Self {
+        Self { ranges: merge(ranges) }
+    }
+}
+
+impl From<SectionRange> for SectionSet {
+    fn from(range: SectionRange) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+impl SectionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[SectionRange] {
+        &self.ranges
+    }
+
+    /// Number of sections covered.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(SectionRange::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        let i = self.ranges.partition_point(|r| r.to() < section);
+        self.ranges.get(i).is_some_and(|r| r.from() <= section)
+    }
+
+    /// The smallest range covering every section of the set.
+    pub fn bounds(&self) -> Option<SectionRange> {
+        Some(SectionRange::new(self.ranges.first()?.from(), self.ranges.last()?.to()))
+    }
+
+    pub fn insert(&mut self, range: SectionRange) {
+        *self = self.union(&Self::from(range));
+    }
+
+    pub fn union(&self, other: &SectionSet) -> SectionSet {
+        self.ranges.iter().chain(&other.ranges).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &SectionSet) -> SectionSet {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            ranges.extend(a.intersection(&b));
+            if a.to() < b.to() { i += 1 } else { j += 1 }
+        }
+        Self { ranges }
+    }
+
+    /// Sections in `self` that are not in `other`.
+    pub fn difference(&self, other: &SectionSet) -> SectionSet {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for &range in &self.ranges {
+            let mut from = range.from();
+            while j < other.ranges.len() && other.ranges[j].to() < from {
+                j += 1;
+            }
+            let mut k = j;
+            let mut exhausted = false;
+            while k < other.ranges.len() && other.ranges[k].from() <= range.to() {
+                let cut = other.ranges[k];
+                if cut.from() > from {
+                    ranges.push(SectionRange::new(from, cut.from() - 1));
+                }
+                match cut.to().checked_add(1) {
+                    Some(next) if cut.to() < range.to() => from = next,
+                    _ => {
+                        exhausted = true;
+                        break;
+                    },
+                }
+                k += 1;
+            }
+            if !exhausted {
+                ranges.push(SectionRange::new(from, range.to()));
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Sections within `within` that the set does not cover.
+    pub fn gaps(&self, within: SectionRange) -> SectionSet {
+        SectionSet::from(within).difference(self)
+    }
+}
+
+#[cfg(test)]
+fn set(ranges: &[(u32, u32)]) -> SectionSet {
+    ranges.iter().map(|&(from, to)| SectionRange::new(from, to)).collect()
+}
+
+#[test]
+fn merge_test() {
+    assert_eq!(set(&[(5, 7), (1, 2), (3, 4), (6, 9), (12, 12)]).ranges(), &[SectionRange::new(1, 9), SectionRange::new(12, 12)]);
+    assert_eq!(set(&[(4, 2)]), SectionSet::new());
+    assert_eq!(set(&[(0, u32::MAX), (3, 4)]).len(), u32::MAX as u64 + 1);
+}
+
+#[test]
+fn set_operations_test() {
+    let a = set(&[(1, 5), (10, 20)]);
+    let b = set(&[(4, 12), (18, 25)]);
+    assert_eq!(a.union(&b), set(&[(1, 25)]));
+    assert_eq!(a.intersection(&b), set(&[(4, 5), (10, 12), (18, 20)]));
+    assert_eq!(a.difference(&b), set(&[(1, 3), (13, 17)]));
+    assert_eq!(b.difference(&a), set(&[(6, 9), (21, 25)]));
+    assert_eq!(a.difference(&set(&[(0, 30)])), SectionSet::new());
+    assert_eq!(set(&[(0, u32::MAX)]).difference(&set(&[(5, u32::MAX)])), set(&[(0, 4)]));
+    assert_eq!(a.len(), 16);
+    assert!(a.contains(10) && a.contains(5) && !a.contains(7));
+}
+
+#[test]
+fn gaps_test() {
+    let cleaned = set(&[(2, 4), (6, 8), (2, 3), (5, 7)]);
+    assert_eq!(cleaned.gaps(SectionRange::new(1, 10)), set(&[(1, 1), (9, 10)]));
+    assert_eq!(set(&[(2, 3), (6, 8)]).gaps(set(&[(2, 3), (6, 8)]).bounds().unwrap()), set(&[(4, 5)]));
+    let mut cleaned = SectionSet::new();
+    cleaned.insert(SectionRange::new(3, 3));
+    assert_eq!(cleaned.bounds(), Some(SectionRange::new(3, 3)));
+}
@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_4::CampCleanup;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = CampCleanup.parse(EXAMPLE).unwrap();
+    assert_eq!(CampCleanup.part_one(&input).unwrap(), 2);
+}
+
+#[test]
+fn example_part_two() {
+    let input = CampCleanup.parse(EXAMPLE).unwrap();
+    assert_eq!(CampCleanup.part_two(&input).unwrap(), 4);
+}
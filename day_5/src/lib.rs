@@ -0,0 +1,270 @@
+use aoc_common::{LineContext, LineError, Solution};
+use slice_deque::SliceDeque;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MoveError {
+    #[error("Expected `move <amount> from <stack> to <stack>`")]
+    Malformed,
+    #[error("Failed parsing number `{0}`")]
+    InvalidNumber(String),
+    #[error("There is no stack {0}")]
+    UnknownStack(usize),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CraneError {
+    #[error("Invalid move on {0}")]
+    InvalidMove(#[from] LineError<MoveError>),
+    #[error("There is no stack {stack}")]
+    UnknownStack { stack: usize },
+    #[error("Stack {stack} does not have enough containers")]
+    EmptyStack { stack: usize },
+}
+
+pub enum CraneType {
+    SingleContainerMove,
+    MultipleContainerMove,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContainerStack {
+    stack: SliceDeque<char>
+}
+
+
+impl ContainerStack {
+    pub fn new() -> Self {
+        Self { stack: SliceDeque::new() }
+    }
+
+    pub fn put_containers(&mut self, containers: &[char]) -> &[char]{
+        for &c in containers {
+            self.stack.push_back(c);
+        }
+        self.slice()
+    }
+
+    pub fn put_multiple_containers(&mut self, containers: &[char]) -> &[char] {
+        for &c in containers.iter().rev() {
+            self.stack.push_back(c);
+        }
+        self.slice()
+    }
+
+    pub fn put_container_on_bottom(&mut self, container: char) {
+        self.stack.push_front(container);
+    }
+
+    pub fn take_containers(&mut self, number: u32) -> Option<Vec<char>> {
+        if self.stack.len() < number as usize {
+            return None;
+        }
+        let mut ret = vec![];
+        for _ in 0..number {
+            ret.push(self.stack.pop_back()?);
+        }
+        Some(ret)
+    }
+
+    pub fn slice(&self) -> &[char] {
+        &self.stack
+    }
+
+    pub fn top(&self) -> Option<char> {
+        self.stack.back().copied()
+    }
+}
+
+#[test]
+fn test_container_stack() {
+    let mut s = ContainerStack::new();
+    s.put_container_on_bottom('G');
+    s.put_container_on_bottom('B');
+    s.put_container_on_bottom('D');
+    s.put_container_on_bottom('C');
+    s.put_container_on_bottom('P');
+    s.put_container_on_bottom('R');
+    assert_eq!(s.slice(), &['R', 'P', 'C', 'D', 'B', 'G']);
+    assert_eq!(s.take_containers(3), Some(vec!['G', 'B', 'D']));
+    assert_eq!(s.take_containers(4), None);
+    assert_eq!(s.slice(), &['R', 'P', 'C']);
+    assert_eq!(s.put_containers(&['G', 'B', 'D']), &['R', 'P', 'C', 'G', 'B', 'D']);
+
+}
+
+pub fn parse_stackline(line: &str, mut stacks: Vec<ContainerStack>) -> Vec<ContainerStack> {
+    for (i, c) in line.chars().enumerate() {
+        if i % 4 == 1 {
+            let stack_index = i / 4;
+            if stacks.len() <= stack_index {
+                for _ in 0..=stack_index - stacks.len() {
+                    stacks.push(ContainerStack::new());
+                }
+            }
+
+            if c != ' ' {
+                stacks[stack_index].put_container_on_bottom(c);
+            }
+        }
+    }
+
+    stacks
+}
+
+#[test]
+fn parse_stackline_test() {
+    let mut stacks = parse_stackline("[G]     [P] [C] [F] [G] [T]", vec![]);
+    stacks = parse_stackline("[B]     [J] [D] [P] [V] [F] [F]", stacks);
+    assert_eq!(stacks[0].slice(), &['B', 'G']);
+    assert_eq!(stacks[1].slice(), &[]);
+    assert_eq!(stacks[2].slice(), &['J', 'P']);
+    assert_eq!(stacks[3].slice(), &['D', 'C']);
+}
+
+/// A single `move N from A to B` step, with zero-based stack indices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move {
+    amount: u32,
+    from: usize,
+    to: usize,
+}
+
+impl Move {
+    pub fn new(amount: u32, from: usize, to: usize) -> Self {
+        Self { amount, from, to }
+    }
+
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn apply(&self, stacks: &mut [ContainerStack], crane_type: &CraneType) -> Result<(), CraneError> {
+        if self.to >= stacks.len() {
+            return Err(CraneError::UnknownStack { stack: self.to + 1 });
+        }
+        let target_containers = stacks.get_mut(self.from)
+            .ok_or(CraneError::UnknownStack { stack: self.from + 1 })?
+            .take_containers(self.amount)
+            .ok_or(CraneError::EmptyStack { stack: self.from + 1 })?;
+        match crane_type {
+            CraneType::SingleContainerMove => stacks[self.to].put_containers(&target_containers),
+            CraneType::MultipleContainerMove => stacks[self.to].put_multiple_containers(&target_containers),
+        };
+        Ok(())
+    }
+}
+
+pub fn parse_move_line(line: &str) -> Result<Move, MoveError> {
+    let split_line = line.split(' ').collect::<Vec<&str>>();
+    if split_line.len() != 6 || split_line[0] != "move" || split_line[2] != "from" || split_line[4] != "to" {
+        return Err(MoveError::Malformed);
+    }
+    let parse_number = |number: &str| number.parse::<usize>().map_err(|_| MoveError::InvalidNumber(number.to_owned()));
+    let amount = split_line[1].parse::<u32>().map_err(|_| MoveError::InvalidNumber(split_line[1].to_owned()))?;
+    let from = parse_number(split_line[3])?.checked_sub(1).ok_or(MoveError::UnknownStack(0))?;
+    let to = parse_number(split_line[5])?.checked_sub(1).ok_or(MoveError::UnknownStack(0))?;
+    Ok(Move { amount, from, to })
+}
+
+#[test]
+fn parse_move_line_test() {
+    let mut stacks = parse_stackline("[G]     [P] [C] [F] [G] [T]", vec![]);
+    stacks = parse_stackline("[B]     [J] [D] [P] [V] [F] [F]", stacks);
+    parse_move_line("move 1 from 1 to 2").unwrap().apply(&mut stacks, &CraneType::SingleContainerMove).unwrap();
+    assert_eq!(stacks[0].slice(), &['B']);
+    assert_eq!(stacks[1].slice(), &['G']);
+    parse_move_line("move 1 from 2 to 3").unwrap().apply(&mut stacks, &CraneType::SingleContainerMove).unwrap();
+    assert_eq!(stacks[1].slice(), &[]);
+    assert_eq!(stacks[2].slice(), &['J', 'P', 'G']);
+    parse_move_line("move 3 from 3 to 2").unwrap().apply(&mut stacks, &CraneType::MultipleContainerMove).unwrap();
+    assert_eq!(stacks[1].slice(), &['J', 'P', 'G']);
+    assert_eq!(parse_move_line("move 1 from 1").unwrap_err(), MoveError::Malformed);
+    assert_eq!(parse_move_line("move 1 from 0 to 2").unwrap_err(), MoveError::UnknownStack(0));
+    assert_eq!(parse_move_line("move 4294967297 from 2 to 1").unwrap_err(), MoveError::InvalidNumber(String::from("4294967297")));
+    assert_eq!(parse_move_line("move 9 from 2 to 1").unwrap().apply(&mut stacks, &CraneType::SingleContainerMove), Err(CraneError::EmptyStack { stack: 2 }));
+}
+
+/// Starting stacks drawing and the rearrangement procedure that follows it.
+#[derive(Debug)]
+pub struct Procedure {
+    stacks: Vec<ContainerStack>,
+    moves: Vec<Move>,
+}
+
+impl Procedure {
+    pub fn new(stacks: Vec<ContainerStack>, moves: Vec<Move>) -> Self {
+        Self { stacks, moves }
+    }
+
+    pub fn stacks(&self) -> &[ContainerStack] {
+        &self.stacks
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+}
+
+pub fn apply_crane(procedure: &Procedure, crane_type: CraneType) -> Result<Vec<ContainerStack>, CraneError> {
+    let mut stacks = procedure.stacks.clone();
+    for m in &procedure.moves {
+        m.apply(&mut stacks, &crane_type)?;
+    }
+    Ok(stacks)
+}
+
+fn top_containers(stacks: &[ContainerStack]) -> Result<String, CraneError> {
+    stacks.iter().enumerate()
+        .map(|(i, s)| s.top().ok_or(CraneError::EmptyStack { stack: i + 1 }))
+        .collect()
+}
+
+pub struct SupplyStacks;
+
+impl Solution for SupplyStacks {
+    type Input = Procedure;
+    type Answer = String;
+    type Error = CraneError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        let mut stacks: Vec<ContainerStack> = vec![];
+        let mut moves = vec![];
+        for (i, l) in input.lines().enumerate() {
+            if l.trim().is_empty() {
+                continue;
+            }
+            if l.starts_with("move") {
+                let m = parse_move_line(l)
+                    .and_then(|m| match [m.from, m.to].into_iter().find(|&stack| stack >= stacks.len()) {
+                        Some(stack) => Err(MoveError::UnknownStack(stack + 1)),
+                        None => Ok(m),
+                    })
+                    .at_line(i + 1, l)?;
+                moves.push(m);
+                continue;
+            }
+            if l.trim().as_bytes()[0].is_ascii_digit() {
+                continue;
+            }
+            stacks = parse_stackline(l, stacks);
+        }
+        Ok(Procedure { stacks, moves })
+    }
+
+    fn part_one(&self, procedure: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        top_containers(&apply_crane(procedure, CraneType::SingleContainerMove)?)
+    }
+
+    fn part_two(&self, procedure: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        top_containers(&apply_crane(procedure, CraneType::MultipleContainerMove)?)
+    }
+}
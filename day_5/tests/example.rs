@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_5::SupplyStacks;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = SupplyStacks.parse(EXAMPLE).unwrap();
+    assert_eq!(SupplyStacks.part_one(&input).unwrap(), "CMZ");
+}
+
+#[test]
+fn example_part_two() {
+    let input = SupplyStacks.parse(EXAMPLE).unwrap();
+    assert_eq!(SupplyStacks.part_two(&input).unwrap(), "MCD");
+}
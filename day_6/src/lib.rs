@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use aoc_common::Solution;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TuningError {
+    #[error("No marker of {0} distinct characters found")]
+    NoMarker(usize),
+}
+
+pub mod queue;
+
+pub fn all_chars_are_different(s: &str) -> bool {
+    let mut existing_chars = HashMap::new();
+    for c in s.chars() {
+        if existing_chars.contains_key(&c) {
+            return false;
+        }
+        existing_chars.insert(c, true);
+    }
+    true
+}
+
+#[test]
+fn all_chars_are_different_test() {
+    assert!(all_chars_are_different("asdf"));
+    assert!(all_chars_are_different("mnbv"));
+    assert!(!all_chars_are_different("asda"));
+    assert!(!all_chars_are_different("asds"));
+    assert!(!all_chars_are_different("amvm"));
+}
+
+/// Number of characters read once the last `buffer_size` characters are all different.
+pub fn find_first_marker_last_character_index(input: &str, buffer_size: usize) -> Option<usize> {
+    let mut queue = queue::CircularQueue::with_capacity(buffer_size);
+
+    for (i, c) in input.chars().enumerate() {
+        queue.push(c);
+        if queue.is_full() && all_chars_are_different(&queue.iter().collect::<String>()) {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+#[test]
+fn find_first_marker_last_character_index_test() {
+    assert_eq!(find_first_marker_last_character_index("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+    assert_eq!(find_first_marker_last_character_index("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+    assert_eq!(find_first_marker_last_character_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), Some(10));
+    assert_eq!(find_first_marker_last_character_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), Some(11));
+
+    assert_eq!(find_first_marker_last_character_index("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Some(19));
+    assert_eq!(find_first_marker_last_character_index("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), Some(23));
+    assert_eq!(find_first_marker_last_character_index("nppdvjthqldpwncqszvftbrmjlhg", 14), Some(23));
+    assert_eq!(find_first_marker_last_character_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), Some(29));
+    assert_eq!(find_first_marker_last_character_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), Some(26));
+}
+
+pub struct TuningTrouble;
+
+impl Solution for TuningTrouble {
+    type Input = String;
+    type Answer = usize;
+    type Error = TuningError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part_one(&self, datastream: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        find_first_marker_last_character_index(datastream, 4).ok_or(TuningError::NoMarker(4))
+    }
+
+    fn part_two(&self, datastream: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        find_first_marker_last_character_index(datastream, 14).ok_or(TuningError::NoMarker(14))
+    }
+}
@@ -29,16 +29,21 @@ impl<T> CircularQueue<T> {
         self.data.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     pub fn is_full(&self) -> bool {
         self.data.len() == self.data.capacity()
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         let (tail, head) = self.data.split_at(self.index);
         head.iter().chain(tail.iter())
     }
 }
 
+#[cfg(test)]
 impl<T: Clone> CircularQueue<T> {
     fn owned_vec(&self) -> Vec<T> {
         let mut ret = vec![];
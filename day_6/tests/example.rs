@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_6::TuningTrouble;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = TuningTrouble.parse(EXAMPLE).unwrap();
+    assert_eq!(TuningTrouble.part_one(&input).unwrap(), 7);
+}
+
+#[test]
+fn example_part_two() {
+    let input = TuningTrouble.parse(EXAMPLE).unwrap();
+    assert_eq!(TuningTrouble.part_two(&input).unwrap(), 19);
+}
@@ -0,0 +1,255 @@
+use std::{collections::{HashMap, HashSet}, path::{Path, PathBuf}};
+
+use aoc_common::{numbered_lines, LineContext, LineError, Solution};
+use path_absolutize::*;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CommandError {
+    #[error("Cannot parse command")]
+    MissingCommand,
+    #[error("Invalid command {0}")]
+    UnknownCommand(String),
+    #[error("Cannot parse path")]
+    MissingPath,
+    #[error("Cannot resolve path {0}")]
+    InvalidPath(String),
+    #[error("Failed getting file or directory name")]
+    MissingName,
+    #[error("Failed parsing size: {0}")]
+    InvalidSize(String),
+    #[error("Directory {0} was listed before being discovered")]
+    UnknownDirectory(String),
+    #[error("{0} is a file, not a directory")]
+    NotADirectory(String),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DeviceError {
+    #[error("Invalid terminal output on {0}")]
+    InvalidOutput(#[from] LineError<CommandError>),
+    #[error("Used space {used} exceeds the disk size {total}")]
+    DiskOverfull { used: usize, total: usize },
+    #[error("No directory is large enough to free {0}")]
+    NoDeletionCandidate(usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List,
+    ChangeDirectory(String),
+}
+
+/// All files and directories keyed by their absolute path.
+pub type FileArena = HashMap<String, Node>;
+
+pub const TOTAL_SPACE:usize = 70000000;
+
+impl Command {
+    pub fn from_line(line: &str) -> Result<Option<Self>, CommandError> {
+        if !Self::is_command(line) {
+            return Ok(None);
+        }
+        let mut split = line.split(' ');
+        split.next();
+        let command = split.next().ok_or(CommandError::MissingCommand)?;
+
+        match command {
+            "ls" => Ok(Some(Command::List)),
+            "cd" => {
+                let path = split.next().ok_or(CommandError::MissingPath)?;
+                Ok(Some(Command::ChangeDirectory(String::from(path))))
+            },
+            _ => Err(CommandError::UnknownCommand(String::from(command)))
+        }
+    }
+
+    pub fn is_command(line: &str) -> bool {
+        line.starts_with("$")
+    }
+
+    pub fn change_directory(cd: &str, current_directory: &mut PathBuf) -> Result<(), CommandError> {
+        *current_directory = current_directory.join(cd).absolutize().map_err(|_| CommandError::InvalidPath(String::from(cd)))?.to_path_buf();
+        Ok(())
+    }
+
+    pub fn execute_command(&self, current_directory: &mut PathBuf) -> Result<(), CommandError> {
+        match self {
+            Command::List => Ok(()),
+            Command::ChangeDirectory(cd) => Self::change_directory(cd, current_directory),
+        }
+    }
+}
+
+pub struct FileProperties {
+    name: String,
+    size: usize,
+}
+
+pub struct DirectoryProperties {
+    name: String,
+    children: HashSet<String>
+}
+
+impl FileProperties {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl DirectoryProperties {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Arena keys of the direct children of this directory.
+    pub fn children(&self) -> &HashSet<String> {
+        &self.children
+    }
+}
+
+pub enum Node {
+    File(FileProperties),
+    Directory(DirectoryProperties),
+}
+
+impl Node {
+    pub fn name(&self) -> &str {
+        match self {
+            Node::File(f) => f.name(),
+            Node::Directory(d) => d.name(),
+        }
+    }
+
+    pub fn get_size(&self, file_arena: &FileArena) -> usize {
+        match self {
+            Node::File(f) => f.size,
+            Node::Directory(d) => d.children.iter().fold(0, |acc, e| acc + file_arena.get(e).unwrap().get_size(file_arena)),
+        }
+    }
+}
+
+pub fn process_directory_list(line: &str, current_directory: &Path, file_arena: &mut FileArena) -> Result<(), CommandError> {
+    let mut line_iter = line.split(' ');
+    let size_or_dir = line_iter.next().unwrap_or_default();
+    let name = line_iter.next().ok_or(CommandError::MissingName)?;
+    let joined_path = current_directory.join(name);
+    let target_path = joined_path.to_str().unwrap();
+    if !file_arena.contains_key(target_path) {
+        let new_node = match size_or_dir == "dir" {
+            true => Node::Directory(DirectoryProperties{ name: String::from(name), children: HashSet::new() }),
+            false => Node::File(FileProperties{ name: String::from(name), size: size_or_dir.parse::<usize>().map_err(|_| CommandError::InvalidSize(String::from(size_or_dir)))? }),
+        };
+        let current_directory = current_directory.to_str().unwrap();
+        match file_arena.get_mut(current_directory) {
+            None => return Err(CommandError::UnknownDirectory(String::from(current_directory))),
+            Some(Node::File(_)) => return Err(CommandError::NotADirectory(String::from(current_directory))),
+            Some(Node::Directory(ref mut props)) => props.children.insert(target_path.to_owned()),
+        };
+        file_arena.insert(target_path.to_owned(), new_node);
+    }
+    Ok(())
+}
+
+pub fn used_space(file_arena: &FileArena) -> usize {
+    let root = file_arena.get("/").unwrap();
+    root.get_size(file_arena)
+}
+
+/// Directories reachable from `dir` (itself included) whose total size is at least `min_size`.
+pub fn find_subdirectories<'a>(dir: &'a Node, file_arena: &'a FileArena, min_size: usize) -> Vec<&'a Node> {
+    let mut ret = vec![];
+    if let Node::Directory(dir_props) = dir {
+        if dir.get_size(file_arena) >= min_size {
+            ret.push(dir);
+            for subdir_name in dir_props.children.iter() {
+                let node = file_arena.get(subdir_name).unwrap();
+                ret = [ret, find_subdirectories(node, file_arena, min_size)].concat();
+            }
+        }
+    }
+    ret
+}
+
+pub struct NoSpaceLeftOnDevice;
+
+impl Solution for NoSpaceLeftOnDevice {
+    type Input = FileArena;
+    type Answer = usize;
+    type Error = DeviceError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        let mut file_arena: FileArena = HashMap::new();
+        file_arena.insert(String::from("/"), Node::Directory(DirectoryProperties{ name: String::from("/"), children: HashSet::new() }));
+        let mut current_directory = PathBuf::from("/");
+        for (line, text) in numbered_lines(input) {
+            if let Some(command) = Command::from_line(text).at_line(line, text)? {
+                command.execute_command(&mut current_directory).at_line(line, text)?;
+            } else {
+                process_directory_list(text, &current_directory, &mut file_arena).at_line(line, text)?;
+            }
+        }
+        Ok(file_arena)
+    }
+
+    fn part_one(&self, file_arena: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        Ok(file_arena.iter()
+            .filter(|(_, value)| matches!(value, Node::Directory(_)) && value.get_size(file_arena) < 100000)
+            .fold(0, |acc, (_, dir)| acc + dir.get_size(file_arena)))
+    }
+
+    fn part_two(&self, file_arena: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        let space_used = used_space(file_arena);
+        let space_available = TOTAL_SPACE.checked_sub(space_used).ok_or(DeviceError::DiskOverfull { used: space_used, total: TOTAL_SPACE })?;
+        let space_required = 30000000usize.saturating_sub(space_available);
+
+        let root = file_arena.get("/").unwrap();
+        let candidates_for_deletion = find_subdirectories(root, file_arena, space_required);
+        let smallest_deletion_candidate = candidates_for_deletion.iter().min_by_key(|d| d.get_size(file_arena)).ok_or(DeviceError::NoDeletionCandidate(space_required))?;
+        Ok(smallest_deletion_candidate.get_size(file_arena))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{Command, CommandError};
+
+    #[test]
+    fn is_command() {
+        assert!(Command::is_command("$ cd"));
+        assert!(Command::is_command("$ ls"));
+        assert!(!Command::is_command("dir something"));
+        assert!(!Command::is_command("45626 cvcbmcm"));
+    }
+
+    #[test]
+    fn from_line() {
+        assert_eq!(Command::from_line("$ ls"), Ok(Some(Command::List)));
+        assert_eq!(Command::from_line("$ cd my_path"), Ok(Some(Command::ChangeDirectory(String::from("my_path")))));
+        assert_eq!(Command::from_line("$ cd some_dir"), Ok(Some(Command::ChangeDirectory(String::from("some_dir")))));
+        assert_eq!(Command::from_line("dir some_dir"), Ok(None));
+        assert_eq!(Command::from_line("$ rm -rf"), Err(CommandError::UnknownCommand(String::from("rm"))));
+        assert_eq!(Command::from_line("$ cd"), Err(CommandError::MissingPath));
+    }
+
+    #[test]
+    fn change_directoy_test() {
+        let mut dir = PathBuf::from("/");
+        Command::change_directory("..", &mut dir).unwrap();
+        assert_eq!(dir, PathBuf::from("/"));
+
+        dir = PathBuf::from("/asdf");
+        Command::change_directory("..", &mut dir).unwrap();
+        assert_eq!(dir, PathBuf::from("/"));
+
+        dir = PathBuf::from("/asdf");
+        Command::change_directory("lkjh", &mut dir).unwrap();
+        assert_eq!(dir, PathBuf::from("/asdf/lkjh"));
+    }
+}
\ No newline at end of file
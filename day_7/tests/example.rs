@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_7::NoSpaceLeftOnDevice;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+    assert_eq!(NoSpaceLeftOnDevice.part_one(&input).unwrap(), 95437);
+}
+
+#[test]
+fn example_part_two() {
+    let input = NoSpaceLeftOnDevice.parse(EXAMPLE).unwrap();
+    assert_eq!(NoSpaceLeftOnDevice.part_two(&input).unwrap(), 24933642);
+}
@@ -0,0 +1,206 @@
+use aoc_common::{numbered_lines, LineContext, LineError, Solution};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ForestError {
+    #[error("Invalid tree height: {0}")]
+    InvalidHeight(char),
+    #[error("Expected a row of {expected} trees, found {found}")]
+    RaggedRow { expected: usize, found: usize },
+}
+
+pub type TreeMap = Vec<Vec<u32>>;
+
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left
+}
+
+impl Direction {
+    pub fn new_position(&self, x: usize, y: usize, map: &TreeMap) -> Option<(usize, usize)> {
+        let coords = match self {
+            Direction::Up => (x, y.checked_sub(1)?),
+            Direction::Right => (x.checked_add(1)?, y),
+            Direction::Down => (x, y.checked_add(1)?),
+            Direction::Left => (x.checked_sub(1)?, y),
+        };
+
+        if map.get(coords.1)?.get(coords.0).is_some() {
+            Some(coords)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_tree_visible(&self, x: usize, y: usize, map: &TreeMap) -> bool {
+        let mut _x = x;
+        let mut _y = y;
+        let tree = get_tree((x, y), map);
+
+        while let Some(new_coords) = self.new_position(_x, _y, map) {
+            let new_tree = get_tree(new_coords, map);
+            if new_tree >= tree {
+                return false;
+            }
+            _x = new_coords.0;
+            _y = new_coords.1;
+        }
+
+        true
+    }
+}
+
+pub fn get_tree(coords: (usize, usize), map: &TreeMap) -> u32 {
+    map[coords.1][coords.0]
+}
+
+pub const DIRECTION: [Direction; 4] = [Direction::Up, Direction::Right, Direction:: Down, Direction::Left];
+
+pub fn is_edge(x: usize, y: usize, map: &TreeMap) -> bool {
+    x == 0 || y == 0 || y == map.len() || y == map.get(y).expect("Y is out of bounds").len()
+}
+
+pub fn is_tree_visible(x: usize, y: usize, map: &TreeMap) -> bool {
+    if is_edge(x, y, map) {
+        return true
+    }
+
+    for d in &DIRECTION {
+        if d.is_tree_visible(x, y, map) {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub fn tree_scenic_score(x: usize, y: usize, map: &TreeMap) -> u32 {
+    if is_edge(x, y, map) {
+        return 0;
+    }
+
+    let tree = get_tree((x, y), map);
+
+    let direction_scores = &DIRECTION.map(|d| {
+        let mut _x = x;
+        let mut _y = y;
+
+        let mut direction_score = 0;
+
+        while let Some(new_coords) = d.new_position(_x, _y, map) {
+            let new_tree = get_tree(new_coords, map);
+            direction_score += 1;
+            if new_tree >= tree {
+                break;
+            }
+            _x = new_coords.0;
+            _y = new_coords.1;
+        }
+        direction_score
+    });
+
+    direction_scores.iter().product()
+}
+
+pub struct TreetopTreeHouse;
+
+impl Solution for TreetopTreeHouse {
+    type Input = TreeMap;
+    type Answer = u32;
+    type Error = LineError<ForestError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error> {
+        let mut map = TreeMap::new();
+        for (line, l) in numbered_lines(input) {
+            let row = l.chars()
+                .map(|height| height.to_digit(10).ok_or(ForestError::InvalidHeight(height)))
+                .collect::<Result<Vec<_>, _>>()
+                .at_line(line, l)?;
+            if let Some(first_row) = map.first() {
+                if first_row.len() != row.len() {
+                    return Err(ForestError::RaggedRow { expected: first_row.len(), found: row.len() }).at_line(line, l);
+                }
+            }
+            map.push(row);
+        }
+        Ok(map)
+    }
+
+    fn part_one(&self, map: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        let mut number_of_visible_trees = 0;
+        for y in 0..map.len() {
+            for x in 0..map[y].len() {
+                if is_tree_visible(x, y, map) {
+                    number_of_visible_trees += 1;
+                }
+            }
+        }
+        Ok(number_of_visible_trees)
+    }
+
+    fn part_two(&self, map: &Self::Input) -> Result<Self::Answer, Self::Error> {
+        let mut max_scenic_score = 0;
+        for y in 0..map.len() {
+            for x in 0..map[y].len() {
+                let score = tree_scenic_score(x, y, map);
+                if score > max_scenic_score {
+                    max_scenic_score = score;
+                }
+            }
+        }
+        Ok(max_scenic_score)
+    }
+}
+
+
+#[cfg(test)]
+fn test_vector() -> TreeMap {
+    vec![
+        vec![3, 0, 3, 7, 3],
+        vec![2, 5, 5, 1, 2],
+        vec![6, 5, 3, 3, 2],
+        vec![3, 3, 5, 4, 9],
+        vec![3, 5, 3, 9, 0],
+    ]
+}
+
+#[test]
+fn is_tree_visible_test() {
+    let map = test_vector();
+    assert!(is_tree_visible(0, 0, &map));
+    assert!(is_tree_visible(1, 0, &map));
+    assert!(is_tree_visible(0, 1, &map));
+    assert!(is_tree_visible(4, 4, &map));
+    assert!(is_tree_visible(2, 4, &map));
+    assert!(is_tree_visible(4, 2, &map));
+    assert!(is_tree_visible(1, 2, &map));
+    assert!(is_tree_visible(2, 1, &map));
+    assert!(is_tree_visible(1, 1, &map));
+    assert!(!is_tree_visible(3, 1, &map));
+    assert!(!is_tree_visible(1, 3, &map));
+    assert!(!is_tree_visible(3, 3, &map));
+    assert!(!is_tree_visible(2, 2, &map));
+}
+
+#[test]
+fn first_part_test() {
+    let map = test_vector();
+    assert_eq!(TreetopTreeHouse.part_one(&map), Ok(21));
+}
+
+#[test]
+fn tree_scenic_score_test() {
+    let map = test_vector();
+    assert_eq!(tree_scenic_score(2, 1, &map), 4);
+    assert_eq!(tree_scenic_score(2, 3, &map), 8);
+}
+
+#[test]
+fn parse_reports_invalid_heights_test() {
+    let error = TreetopTreeHouse.parse("30373\n2551x\n65332").unwrap_err();
+    assert_eq!(error, LineError { line: 2, text: String::from("2551x"), kind: ForestError::InvalidHeight('x') });
+    let error = TreetopTreeHouse.parse("30373\n2551\n65332").unwrap_err();
+    assert_eq!(error.kind, ForestError::RaggedRow { expected: 5, found: 4 });
+}
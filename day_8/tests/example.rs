@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day_8::TreetopTreeHouse;
+
+const EXAMPLE: &str = include_str!("../src/test.txt");
+
+#[test]
+fn example_part_one() {
+    let input = TreetopTreeHouse.parse(EXAMPLE).unwrap();
+    assert_eq!(TreetopTreeHouse.part_one(&input).unwrap(), 21);
+}
+
+#[test]
+fn example_part_two() {
+    let input = TreetopTreeHouse.parse(EXAMPLE).unwrap();
+    assert_eq!(TreetopTreeHouse.part_two(&input).unwrap(), 8);
+}